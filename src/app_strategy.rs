@@ -1,13 +1,19 @@
 //! These strategies require you to provide some information on your application, and they will in turn locate the configuration/data/cache directory specifically for your application.
 
-use std::ffi::OsStr;
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
-use crate::HomeDirError;
+use crate::fs::{DryRunFs, FileSystem, StdFs, DEFAULT_DIR_MODE, PRIVATE_DIR_MODE};
+use crate::{ConfigScope, CreationError, DirClassification, DirKind, DirSource, ResolvedDirs};
 
 /// The arguments to the creator method of an [`AppStrategy`](trait.AppStrategy.html).
+///
+/// With the `serde` feature enabled, this implements `Serialize` & `Deserialize` using the field names below.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AppStrategyArgs {
     /// The top level domain of the application, e.g. `com`, `org`, or `io.github`.
     pub top_level_domain: String,
@@ -15,10 +21,220 @@ pub struct AppStrategyArgs {
     pub author: String,
     /// The application’s name. This should be capitalised if appropriate.
     pub app_name: String,
+    /// How [`unixy_name`](#method.unixy_name) derives a folder name from `app_name`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub naming_strategy: NamingStrategy,
+    /// The application’s bundle identifier, e.g. `com.example.MyApp`. When set, this is used verbatim instead of the one [`bundle_id`](#method.bundle_id) would compose from the fields above.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bundle_id: Option<String>,
+    /// What the strategies’ constructors do with folder names derived from the fields above which are too long for the filesystem.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name_length_policy: NameLengthPolicy,
+}
+
+/// Determines how [`AppStrategyArgs::unixy_name`](struct.AppStrategyArgs.html#method.unixy_name) turns the application’s name into a folder name.
+/// This is used by the [`Xdg`](struct.Xdg.html) & [`Unix`](struct.Unix.html) strategies.
+///
+/// Each space is replaced individually and no other characters are touched, so leading & trailing spaces are kept as separators, unicode letters are lowercased according to Unicode rules, and an empty name stays empty.
+///
+/// ```
+/// use etcetera::app_strategy::{AppStrategyArgs, NamingStrategy};
+///
+/// let strategy_args = AppStrategyArgs {
+///     app_name: "Frobnicator Plus Ünicode".to_string(),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(strategy_args.unixy_name(), "frobnicator-plus-ünicode");
+/// assert_eq!(
+///     strategy_args.clone().with_naming_strategy(NamingStrategy::Snake).unixy_name(),
+///     "frobnicator_plus_ünicode"
+/// );
+/// assert_eq!(
+///     strategy_args.clone().with_naming_strategy(NamingStrategy::Verbatim).unixy_name(),
+///     "Frobnicator Plus Ünicode"
+/// );
+/// assert_eq!(
+///     AppStrategyArgs { app_name: " Frobnicator ".to_string(), ..Default::default() }.unixy_name(),
+///     "-frobnicator-"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum NamingStrategy {
+    /// Lowercases the name and replaces spaces with hyphens, e.g. `frobnicator-plus`. This is the default.
+    #[default]
+    Kebab,
+    /// Lowercases the name and replaces spaces with underscores, e.g. `frobnicator_plus`.
+    Snake,
+    /// Uses the name as-is, e.g. `Frobnicator Plus`.
+    Verbatim,
+}
+
+/// Determines what the app strategies’ constructors do with the folder names they derive from an [`AppStrategyArgs`](struct.AppStrategyArgs.html), e.g. the [`unixy_name`](struct.AppStrategyArgs.html#method.unixy_name), when they are longer than `max_len` bytes.
+///
+/// Most filesystems limit a single folder name to 255 bytes, and exceeding this only fails much later, when a directory is created, with an obscure “file name too long” error. By default, such names are rejected up front instead.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, NameLengthPolicy, Xdg};
+/// use etcetera::CreationError;
+///
+/// let strategy_args = AppStrategyArgs::new("Frobnicator ".repeat(30));
+/// assert!(matches!(
+///     Xdg::new(strategy_args.clone()),
+///     Err(CreationError::InvalidAppName { .. })
+/// ));
+///
+/// let app_strategy = Xdg::new(strategy_args.clone().with_name_length_policy(
+///     NameLengthPolicy::Truncate { max_len: 32 },
+/// )).unwrap();
+/// assert!(app_strategy.config_dir().ends_with("frobnicator-frobnicator-frobnica"));
+///
+/// // The hash keeps apart names which only differ after the cut.
+/// let app_strategy = Xdg::new(strategy_args.with_name_length_policy(
+///     NameLengthPolicy::TruncateWithHash { max_len: 32 },
+/// )).unwrap();
+/// let folder_name = app_strategy.config_dir().file_name().unwrap().to_str().unwrap().to_string();
+/// assert_eq!(folder_name.len(), 32);
+/// assert!(folder_name.starts_with("frobnicator-frobnicator-"));
+///
+/// // There must be room for more than the hash.
+/// assert!(matches!(
+///     Xdg::new(AppStrategyArgs::new("Frobnicator ".repeat(30)).with_name_length_policy(
+///         NameLengthPolicy::TruncateWithHash { max_len: 9 },
+///     )),
+///     Err(CreationError::InvalidAppName { .. })
+/// ));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum NameLengthPolicy {
+    /// Fails with [`CreationError::InvalidAppName`](../enum.CreationError.html#variant.InvalidAppName). This is the default, with a `max_len` of 255.
+    Reject {
+        /// The maximum length of a folder name, in bytes.
+        max_len: usize,
+    },
+    /// Cuts the name down to `max_len` bytes, on a character boundary.
+    Truncate {
+        /// The maximum length of a folder name, in bytes.
+        max_len: usize,
+    },
+    /// Cuts the name down like `Truncate`, but ends it with a hyphen & a hash of the whole name, so that different long names stay different.
+    TruncateWithHash {
+        /// The maximum length of a folder name, in bytes. It must leave room for the 9 bytes of the hash & at least one byte of the name, otherwise long names are rejected like with `Reject`.
+        max_len: usize,
+    },
+}
+
+impl Default for NameLengthPolicy {
+    fn default() -> Self {
+        Self::Reject { max_len: 255 }
+    }
+}
+
+impl NameLengthPolicy {
+    // Applies the policy to a single folder name.
+    fn apply(self, name: String) -> Result<String, CreationError> {
+        let (max_len, hash) = match self {
+            Self::Reject { max_len }
+            | Self::Truncate { max_len }
+            | Self::TruncateWithHash { max_len }
+                if name.len() <= max_len =>
+            {
+                return Ok(name);
+            }
+            Self::Reject { .. } => {
+                return Err(CreationError::InvalidAppName {
+                    app_name: name,
+                    reason: "the name is too long to be used as a folder name",
+                })
+            }
+            Self::Truncate { max_len } => (max_len, None),
+            Self::TruncateWithHash { max_len } => (max_len, Some(format!("-{:08x}", fnv1a(&name)))),
+        };
+
+        let hash_len = hash.as_deref().map_or(0, str::len);
+        if hash_len > 0 && max_len <= hash_len {
+            return Err(CreationError::InvalidAppName {
+                app_name: name,
+                reason: "the maximum length leaves no room for the name besides its hash",
+            });
+        }
+
+        let mut len = max_len - hash_len;
+        while !name.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        let mut truncated = name[..len].to_string();
+        // A cut could leave a trailing dot or space behind, which Windows doesn’t allow.
+        if cfg!(windows) {
+            truncated.truncate(truncated.trim_end_matches(['.', ' ']).len());
+        }
+        truncated.extend(hash);
+
+        validate_name(&truncated)?;
+        Ok(truncated)
+    }
+}
+
+// The 32-bit FNV-1a hash. Unlike `DefaultHasher`, it is guaranteed not to change between releases of Rust, so truncated folder names stay the same.
+fn fnv1a(name: &str) -> u32 {
+    name.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
 }
 
 impl AppStrategyArgs {
-    /// Constructs a bunde identifier from an `AppStrategyArgs`.
+    /// Creates an `AppStrategyArgs` for the given application name, leaving the top level domain & the author empty.
+    /// Use [`with_top_level_domain`](#method.with_top_level_domain) & [`with_author`](#method.with_author) to fill them in.
+    ///
+    /// Empty fields are left out of the [`bundle_id`](#method.bundle_id), so the Apple strategy will use `Frobnicator-Plus` rather than `org.acme-corp.Frobnicator-Plus` if the application’s name is all you provide. The other strategies only use the author in their paths on Windows, where an empty author means that the application’s folder sits directly in `AppData`.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    ///
+    /// let strategy_args = AppStrategyArgs::new("Frobnicator Plus");
+    /// assert_eq!(strategy_args.bundle_id(), "Frobnicator-Plus");
+    ///
+    /// let strategy_args = strategy_args.with_top_level_domain("org").with_author("Acme Corp");
+    /// assert_eq!(
+    ///     strategy_args,
+    ///     AppStrategyArgs {
+    ///         top_level_domain: "org".to_string(),
+    ///         author: "Acme Corp".to_string(),
+    ///         app_name: "Frobnicator Plus".to_string(),
+    ///         ..Default::default()
+    ///     }
+    /// );
+    /// assert_eq!(strategy_args.bundle_id(), "org.acme-corp.Frobnicator-Plus");
+    /// ```
+    pub fn new(app_name: impl Into<String>) -> Self {
+        Self {
+            app_name: app_name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the top level domain of the application.
+    pub fn with_top_level_domain(mut self, top_level_domain: impl Into<String>) -> Self {
+        self.top_level_domain = top_level_domain.into();
+        self
+    }
+
+    /// Sets the name of the author of the application.
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    /// Constructs a bunde identifier from an `AppStrategyArgs`, which is its [`reverse_domain_name`](#method.reverse_domain_name).
+    /// If the [`bundle_id`](#structfield.bundle_id) field is set, it is returned as-is instead.
     ///
     /// ```
     /// use etcetera::app_strategy::AppStrategyArgs;
@@ -27,19 +243,53 @@ impl AppStrategyArgs {
     ///     top_level_domain: "org".to_string(),
     ///     author: "Acme Corp".to_string(),
     ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
     /// };
     ///
     /// assert_eq!(strategy_args.bundle_id(), "org.acme-corp.Frobnicator-Plus".to_string());
+    /// assert_eq!(
+    ///     strategy_args.with_bundle_id("com.example.Frobnicator").bundle_id(),
+    ///     "com.example.Frobnicator".to_string()
+    /// );
     /// ```
     pub fn bundle_id(&self) -> String {
+        match &self.bundle_id {
+            Some(bundle_id) => bundle_id.clone(),
+            None => self.reverse_domain_name(),
+        }
+    }
+
+    /// Constructs a reverse-DNS identifier for your application, e.g. for logs or telemetry, which is the same on every platform.
+    /// This is what the Apple strategies use as the bundle identifier, unless the [`bundle_id`](#structfield.bundle_id) field is set, which is ignored here.
+    ///
+    /// Every space in the author & application name is replaced by a hyphen, and the author is lowercased. The application name keeps its case, as the Apple strategies have always named their folders this way. Other characters, including dots, are kept as they are. Empty parts are skipped, and the rest is joined with dots.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    ///
+    /// let strategy_args = AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(strategy_args.reverse_domain_name(), "org.acme-corp.Frobnicator-Plus");
+    /// assert_eq!(strategy_args.reverse_domain_name(), strategy_args.bundle_id());
+    /// assert_eq!(
+    ///     strategy_args.clone().with_bundle_id("com.example.Frobnicator").reverse_domain_name(),
+    ///     "org.acme-corp.Frobnicator-Plus"
+    /// );
+    ///
+    /// let strategy_args = AppStrategyArgs::new("Frobnicator Plus");
+    /// assert_eq!(strategy_args.reverse_domain_name(), "Frobnicator-Plus");
+    /// ```
+    pub fn reverse_domain_name(&self) -> String {
         let author = self.author.to_lowercase().replace(' ', "-");
         let app_name = self.app_name.replace(' ', "-");
-        let mut parts = vec![
-            self.top_level_domain.as_str(),
-            author.as_str(),
-            app_name.as_str(),
-        ];
-        parts.retain(|part| !part.is_empty());
+        let parts = [self.top_level_domain.as_str(), &author, &app_name];
+
+        let parts: Vec<_> = parts.into_iter().filter(|part| !part.is_empty()).collect();
         parts.join(".")
     }
 
@@ -52,13 +302,174 @@ impl AppStrategyArgs {
     ///     top_level_domain: "org".to_string(),
     ///     author: "Acme Corp".to_string(),
     ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
     /// };
     ///
     /// assert_eq!(strategy_args.unixy_name(), "frobnicator-plus".to_string());
     /// ```
     pub fn unixy_name(&self) -> String {
-        self.app_name.to_lowercase().replace(' ', "-")
+        match self.naming_strategy {
+            NamingStrategy::Kebab => self.app_name.to_lowercase().replace(' ', "-"),
+            NamingStrategy::Snake => self.app_name.to_lowercase().replace(' ', "_"),
+            NamingStrategy::Verbatim => self.app_name.clone(),
+        }
+    }
+
+    /// Checks that the application’s name, and the explicit bundle identifier if there is one, can be used as folder names. The app strategies’ constructors call this for you.
+    ///
+    /// The name must not be empty or only whitespace, must not be `.` or `..`, and must not contain path separators (`/` or `\`) or NUL characters.
+    /// On Windows, the characters `<`, `>`, `:`, `"`, `|`, `?` & `*`, control characters, and trailing dots or spaces are rejected too.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategyArgs, Xdg};
+    /// use etcetera::CreationError;
+    ///
+    /// let strategy_args = AppStrategyArgs {
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(strategy_args.validate().is_ok());
+    ///
+    /// for app_name in ["", "  ", "..", "frobnicator/plus", "frobnicator\\plus"] {
+    ///     let strategy_args = AppStrategyArgs {
+    ///         app_name: app_name.to_string(),
+    ///         ..Default::default()
+    ///     };
+    ///     assert!(matches!(
+    ///         Xdg::new(strategy_args),
+    ///         Err(CreationError::InvalidAppName { .. })
+    ///     ));
+    /// }
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<(), CreationError> {
+        validate_name(&self.app_name)?;
+        if let Some(bundle_id) = &self.bundle_id {
+            validate_name(bundle_id)?;
+        }
+
+        Ok(())
     }
+
+    /// Sets the [`NamingStrategy`](enum.NamingStrategy.html) used by [`unixy_name`](#method.unixy_name).
+    pub fn with_naming_strategy(mut self, naming_strategy: NamingStrategy) -> Self {
+        self.naming_strategy = naming_strategy;
+        self
+    }
+
+    /// Sets the bundle identifier used by the [`Apple`](struct.Apple.html) strategy, overriding the one composed from the other fields.
+    pub fn with_bundle_id(mut self, bundle_id: impl Into<String>) -> Self {
+        self.bundle_id = Some(bundle_id.into());
+        self
+    }
+
+    /// Sets the [`NameLengthPolicy`](enum.NameLengthPolicy.html) applied to the folder names derived from these arguments.
+    pub fn with_name_length_policy(mut self, name_length_policy: NameLengthPolicy) -> Self {
+        self.name_length_policy = name_length_policy;
+        self
+    }
+
+    // Applies the name length policy to a folder name derived from these arguments.
+    pub(crate) fn folder_name(&self, name: String) -> Result<String, CreationError> {
+        self.name_length_policy.apply(name)
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), CreationError> {
+    validate_component(name).map_err(|reason| CreationError::InvalidAppName {
+        app_name: name.to_string(),
+        reason,
+    })
+}
+
+// Joins `name` to the system’s temporary directory. On Unix, that is usually shared by all users, so the first component of `name` is suffixed with the user’s ID, like the fallback runtime directory, for every user to get a folder of their own.
+pub(crate) fn user_temp_dir(name: impl AsRef<Path>) -> PathBuf {
+    let temp_dir = std::env::temp_dir();
+
+    #[cfg(unix)]
+    {
+        let mut components = name.as_ref().components();
+        if let Some(first) = components.next() {
+            let mut first = first.as_os_str().to_os_string();
+            first.push(format!("-{}", crate::effective_uid()));
+
+            let rest = components.as_path();
+            return if rest.as_os_str().is_empty() {
+                temp_dir.join(first)
+            } else {
+                temp_dir.join(first).join(rest)
+            };
+        }
+    }
+
+    temp_dir.join(name)
+}
+
+// Creates `path` & its missing parents. The folder directly inside the system’s temporary directory is created privately, or checked if it exists, so that other users can neither read it nor plant it beforehand.
+fn create_temp_dir(path: &Path) -> io::Result<()> {
+    let system_temp_dir = std::env::temp_dir();
+    if let Some(first) = path
+        .strip_prefix(&system_temp_dir)
+        .ok()
+        .and_then(|rest| rest.components().next())
+    {
+        crate::base_strategy::create_private_dir(&system_temp_dir.join(first))?;
+    }
+
+    std::fs::create_dir_all(path)
+}
+
+// Creates a new folder inside `parent`, named `prefix` followed by a name no other call has used, & returns its path. `parent` must already exist.
+pub(crate) fn create_unique_dir(parent: &Path, prefix: &str) -> io::Result<PathBuf> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    // The process ID makes the names unique across processes, and the counter within one.
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    loop {
+        let name = format!(
+            "{}{}-{}",
+            prefix,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = parent.join(name);
+
+        match std::fs::create_dir(&path) {
+            Ok(()) => return Ok(path),
+            // This is left over from an earlier process with the same ID.
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+// Checks that `name` can be used as a single folder name, returning why not otherwise.
+pub(crate) fn validate_component(name: &str) -> Result<(), &'static str> {
+    if name.trim().is_empty() {
+        return Err("the name is empty");
+    }
+    if name == "." || name == ".." {
+        return Err("the name refers to a special directory");
+    }
+    if name.contains(['/', '\\']) {
+        return Err("the name contains a path separator");
+    }
+    if name.contains('\0') {
+        return Err("the name contains a NUL character");
+    }
+    if cfg!(windows) {
+        if name.contains(|c: char| c.is_control() || "<>:\"|?*".contains(c)) {
+            return Err("the name contains a character that is reserved on Windows");
+        }
+        if name.ends_with(['.', ' ']) {
+            return Err("the name ends with a dot or a space");
+        }
+    }
+
+    Ok(())
 }
 
 macro_rules! in_dir_method {
@@ -74,102 +485,1623 @@ macro_rules! in_dir_method {
     }};
 }
 
+macro_rules! file_method {
+    ($self: ident, $path_extra: expr, $dir_method_name: ident) => {{
+        let path = $self.$dir_method_name().join(Path::new(&$path_extra));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(path)
+    }};
+    (opt: $self: ident, $path_extra: expr, $dir_method_name: ident, $missing: literal) => {{
+        let path = $self
+            .$dir_method_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, $missing))?
+            .join(Path::new(&$path_extra));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(path)
+    }};
+}
+
+macro_rules! create_dir_method {
+    ($self: ident, $fs: expr, $dir_method_name: ident, $mode: expr) => {{
+        let path = $self.$dir_method_name();
+        $fs.create_dir_all_with_mode(&path, $mode)?;
+        Ok(path)
+    }};
+    (opt: $self: ident, $fs: expr, $dir_method_name: ident, $mode: expr) => {{
+        match $self.$dir_method_name() {
+            Some(path) => {
+                $fs.create_dir_all_with_mode(&path, $mode)?;
+                Ok(Some(path))
+            }
+            None => Ok(None),
+        }
+    }};
+}
+
 /// Allows applications to retrieve the paths of configuration, data, and cache directories specifically for them.
 pub trait AppStrategy {
     /// Gets the home directory of the current user.
     fn home_dir(&self) -> &Path;
 
+    /// Gets an owned copy of [`home_dir`](#tymethod.home_dir), e.g. to keep it after a temporary strategy is gone.
+    fn home_dir_owned(&self) -> PathBuf {
+        self.home_dir().to_path_buf()
+    }
+
+    /// Gets the value of the environment variable `key` as this strategy sees it, i.e. from its [`EnvProvider`](../env/trait.EnvProvider.html) if it has one, or from the current process otherwise.
+    /// See [`BaseStrategy::env_var_os`](../base_strategy/trait.BaseStrategy.html#method.env_var_os) for more information.
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+
     /// Gets the configuration directory for your application.
     fn config_dir(&self) -> PathBuf;
 
     /// Gets the data directory for your application.
     fn data_dir(&self) -> PathBuf;
 
+    /// Gets the machine-specific data directory for your application, for data which shouldn’t follow the user to other machines.
+    ///
+    /// For the [`Windows`](struct.Windows.html) strategy, [`data_dir`](#tymethod.data_dir) is part of the roaming profile (`AppData\Roaming`), so this is inside `AppData\Local` instead. Every other strategy has no such distinction, so this is the same as `data_dir`.
+    fn local_data_dir(&self) -> PathBuf {
+        self.data_dir()
+    }
+
     /// Gets the cache directory for your application.
     fn cache_dir(&self) -> PathBuf;
 
     /// Gets the state directory for your application.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) & [`Unix`](struct.Unix.html) strategies support
+    /// Currently, only the [`Xdg`](struct.Xdg.html), [`Unix`](struct.Unix.html), [`Windows`](struct.Windows.html) & [`Apple`](struct.Apple.html) strategies support
     /// this.
     fn state_dir(&self) -> Option<PathBuf>;
 
     /// Gets the runtime directory for your application.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) & [`Unix`](struct.Unix.html) strategies support
+    /// Currently, only the [`Xdg`](struct.Xdg.html), [`Unix`](struct.Unix.html) & [`Windows`](struct.Windows.html) strategies support
     /// this.
     ///
     /// Note: The [XDG Base Directory Specification](spec) places additional requirements on this
-    /// directory related to ownership, permissions, and persistence. This library does not check
-    /// these requirements.
+    /// directory related to ownership, permissions, and persistence. By default, this library does
+    /// not check these requirements; enable [`Xdg::with_runtime_dir_validation`](struct.Xdg.html#method.with_runtime_dir_validation)
+    /// to check the ownership & permissions on Unix, in which case an invalid `XDG_RUNTIME_DIR` is ignored.
     ///
     /// [spec]: https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html
     fn runtime_dir(&self) -> Option<PathBuf>;
 
-    /// Constructs a path inside your application’s configuration directory to which a path of your choice has been appended.
-    fn in_config_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
-        in_dir_method!(self, path, config_dir)
+    /// Gets the runtime directory for your application, or a fallback inside the system’s temporary directory if the strategy has none.
+    ///
+    /// The fallback is `<app name>-<uid>` inside [`std::env::temp_dir`] on Unix, and `<app name>` inside it elsewhere. It is created if it doesn’t exist yet, and an existing one is only reused if it is safe to do so.
+    /// See [`BaseStrategy::runtime_dir_or_fallback`](../base_strategy/trait.BaseStrategy.html#method.runtime_dir_or_fallback) for the security implications.
+    ///
+    /// Strategies outside of this crate have no fallback by default, so this returns a [`NotFound`](io::ErrorKind::NotFound) error if they have no runtime directory.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use std::collections::HashMap;
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::<String, String>::new()).unwrap();
+    ///
+    /// let runtime_dir = app_strategy.runtime_dir_or_fallback().unwrap();
+    ///
+    /// #[cfg(unix)]
+    /// assert_eq!(
+    ///     runtime_dir,
+    ///     std::env::temp_dir().join(format!("frobnicator-plus-{}", etcetera::effective_uid()))
+    /// );
+    /// assert!(runtime_dir.is_dir());
+    /// # }
+    /// ```
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        self.runtime_dir().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "the strategy has no runtime directory",
+            )
+        })
     }
 
-    /// Constructs a path inside your application’s data directory to which a path of your choice has been appended.
-    fn in_data_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
-        in_dir_method!(self, path, data_dir)
+    /// Gets a temporary directory for your application, i.e. your application’s folder inside [`std::env::temp_dir`], which respects `TMPDIR` on Unix and `TEMP` on Windows.
+    /// This doesn’t create the directory. Its contents may be cleaned up by the system at any time, so it shouldn’t hold anything which must outlive the process.
+    ///
+    /// By default, the folder is named after the last component of [`config_dir`](#tymethod.config_dir); the strategies in this crate use the same folder name as for their other directories. On Unix, where the temporary directory is usually shared by all users, the folder name is suffixed with the user’s [ID](../fn.effective_uid.html), like the fallback of [`runtime_dir_or_fallback`](#method.runtime_dir_or_fallback), so that every user gets a folder of their own.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    ///
+    /// let app_strategy = Xdg::new(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }).unwrap();
+    ///
+    /// #[cfg(unix)]
+    /// assert_eq!(
+    ///     app_strategy.temp_dir(),
+    ///     std::env::temp_dir().join(format!("frobnicator-plus-{}", etcetera::effective_uid()))
+    /// );
+    /// #[cfg(not(unix))]
+    /// assert_eq!(
+    ///     app_strategy.temp_dir(),
+    ///     std::env::temp_dir().join("frobnicator-plus")
+    /// );
+    ///
+    /// let scratch_dir = app_strategy.scratch_dir().unwrap();
+    /// assert!(scratch_dir.is_dir());
+    /// assert!(scratch_dir.starts_with(app_strategy.temp_dir()));
+    /// assert_ne!(scratch_dir, app_strategy.scratch_dir().unwrap());
+    ///
+    /// // Other users can’t look inside.
+    /// #[cfg(unix)]
+    /// {
+    ///     use std::os::unix::fs::PermissionsExt;
+    ///     let mode = std::fs::metadata(app_strategy.temp_dir()).unwrap().permissions().mode();
+    ///     assert_eq!(mode & 0o077, 0);
+    /// }
+    /// # }
+    /// ```
+    fn temp_dir(&self) -> PathBuf {
+        let config_dir = self.config_dir();
+        user_temp_dir(config_dir.file_name().unwrap_or_default())
     }
 
-    /// Constructs a path inside your application’s cache directory to which a path of your choice has been appended.
-    fn in_cache_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
-        in_dir_method!(self, path, cache_dir)
+    /// Creates a new, uniquely-named directory inside [`temp_dir`](#method.temp_dir) (and any missing parents) and returns its path.
+    /// On Unix, the folder directly inside the system’s temporary directory is created so that only the current user can access it; if it already exists but belongs to someone else, or other users can access it, this returns an error rather than using it.
+    /// Every call creates a different directory. Removing it once you are done with it is up to you.
+    fn scratch_dir(&self) -> io::Result<PathBuf> {
+        let temp_dir = self.temp_dir();
+        create_temp_dir(&temp_dir)?;
+        create_unique_dir(&temp_dir, "")
     }
 
-    /// Constructs a path inside your application’s state directory to which a path of your choice has been appended.
+    /// Gets the log directory for your application.
+    /// By default, this is `logs` inside your application’s [state directory](#tymethod.state_dir), or `None` if there is none; the built-in strategies override it with their platform’s convention.
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.state_dir().map(|state_dir| state_dir.join("logs"))
+    }
+
+    /// Gets the directory in which your application’s executables should be installed.
     ///
-    /// Currently, this is only implemented for the [`Xdg`](struct.Xdg.html) strategy.
-    fn in_state_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
-        in_dir_method!(opt: self, path, state_dir)
+    /// Note that for the [`Xdg`](struct.Xdg.html) strategy, this is the user’s shared binary directory (`~/.local/bin`), i.e. no application-specific folder is appended. This is also the default, for strategies which don’t override it.
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir().join(".local/bin/")
     }
 
-    /// Constructs a path inside your application’s runtime directory to which a path of your choice has been appended.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) & [`Unix`](struct.Unix.html) strategies support
-    /// this.
+    /// Gets one of your application’s directories, selected by `kind`, e.g. to loop over [`DirKind::ALL`](../enum.DirKind.html#associatedconstant.ALL). This returns `None` for directories the strategy doesn’t have.
+    fn dir(&self, kind: DirKind) -> Option<PathBuf> {
+        match kind {
+            DirKind::Home => Some(self.home_dir().to_path_buf()),
+            DirKind::Config => Some(self.config_dir()),
+            DirKind::Data => Some(self.data_dir()),
+            DirKind::Cache => Some(self.cache_dir()),
+            DirKind::State => self.state_dir(),
+            DirKind::Runtime => self.runtime_dir(),
+            DirKind::Log => self.log_dir(),
+            DirKind::Bin => Some(self.bin_dir()),
+        }
+    }
+
+    /// Gets the configuration directory for your application along with where it came from, i.e. where the base directory it lies in came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
     ///
-    /// See the note in [`runtime_dir`](#method.runtime_dir) for more information.
-    fn in_runtime_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
-        in_dir_method!(opt: self, path, runtime_dir)
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use etcetera::DirSource;
+    /// use std::collections::HashMap;
+    /// use std::path::Path;
+    ///
+    /// let config_path = if cfg!(windows) {
+    ///     "C:\\my_config_location\\"
+    /// } else {
+    ///     "/my_config_location/"
+    /// };
+    ///
+    /// let app_strategy = Xdg::new_with_env(
+    ///     AppStrategyArgs {
+    ///         top_level_domain: "org".to_string(),
+    ///         author: "Acme Corp".to_string(),
+    ///         app_name: "Frobnicator Plus".to_string(),
+    ///         ..Default::default()
+    ///     },
+    ///     HashMap::from([("XDG_CONFIG_HOME", config_path)]),
+    /// ).unwrap();
+    ///
+    /// let (config_dir, source) = app_strategy.config_dir_with_source();
+    /// assert_eq!(config_dir, Path::new(config_path).join("frobnicator-plus"));
+    /// assert_eq!(
+    ///     format!("config_dir is {} (from {source})", config_dir.display()),
+    ///     format!("config_dir is {} (from $XDG_CONFIG_HOME)", config_dir.display())
+    /// );
+    /// # }
+    /// ```
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        (self.config_dir(), DirSource::Default)
     }
-}
 
-macro_rules! create_strategies {
-    ($native: ty, $app: ty) => {
-        /// Returns the current OS’s native [`AppStrategy`](trait.AppStrategy.html).
-        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, [`Apple`](struct.Apple.html) on macOS & iOS, and [`Xdg`](struct.Xdg.html) everywhere else.
-        /// This is the convention used by most GUI applications.
-        pub fn choose_native_strategy(args: AppStrategyArgs) -> Result<$native, HomeDirError> {
-            <$native>::new(args)
+    /// Gets the data directory for your application along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        (self.data_dir(), DirSource::Default)
+    }
+
+    /// Gets the cache directory for your application along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        (self.cache_dir(), DirSource::Default)
+    }
+
+    /// Gets the state directory for your application along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.state_dir().map(|dir| (dir, DirSource::Default))
+    }
+
+    /// Gets the runtime directory for your application along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.runtime_dir().map(|dir| (dir, DirSource::Default))
+    }
+
+    /// Gets the preferences directory for your application.
+    ///
+    /// For the [`Apple`](struct.Apple.html) strategy, this is always `~/Library/Preferences/<bundle id>`, which is where `CFPreferences`/`UserDefaults` expect your preferences to be.
+    /// Every other strategy has no separate notion of preferences, so this is the same as [`config_dir`](#tymethod.config_dir).
+    fn preference_dir(&self) -> PathBuf {
+        self.config_dir()
+    }
+
+    /// Gets the directory for your application’s secrets, such as credentials & access tokens, which must be kept apart from the configuration users share or back up. Use [`create_secret_dir`](#method.create_secret_dir) to create it.
+    ///
+    /// | Strategy                         | Location                                                     |
+    /// |----------------------------------|--------------------------------------------------------------|
+    /// | [`Apple`](struct.Apple.html)     | `~/Library/Application Support/<bundle id>/Secrets/`         |
+    /// | [`Windows`](struct.Windows.html) | `%LOCALAPPDATA%\<author>\<app name>\secrets`                 |
+    /// | Every other strategy             | `secrets/` inside the [state directory](#tymethod.state_dir) |
+    ///
+    /// On Windows, the local app data folder is used as it never roams to other machines. This is `None` if the strategy has no state directory.
+    fn secret_dir(&self) -> Option<PathBuf> {
+        self.state_dir().map(|state_dir| state_dir.join("secrets/"))
+    }
+
+    /// Gets the single folder all of your application’s own directories live in, e.g. for an uninstaller or a backup tool to handle the application’s whole footprint at once.
+    ///
+    /// Only the [`Unix`](struct.Unix.html) strategy has such a folder, `~/.myapp/`. Every other strategy spreads your application’s directories over several parents shared with other applications, e.g. `~/.config/` & `~/.cache/`, so this is `None` for them. The temporary & system-wide directories are never part of the root.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "xdg", feature = "unix"))]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Unix, Xdg};
+    ///
+    /// let strategy_args = AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let app_strategy = Unix::new(strategy_args.clone()).unwrap();
+    /// let app_root = app_strategy.app_root().unwrap();
+    /// assert_eq!(app_root, app_strategy.home_dir().join(".frobnicator-plus"));
+    /// assert!(app_strategy.data_dir().starts_with(&app_root));
+    /// assert!(app_strategy.cache_dir().starts_with(&app_root));
+    ///
+    /// assert_eq!(Xdg::new(strategy_args).unwrap().app_root(), None);
+    /// # }
+    /// ```
+    fn app_root(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets [`bin_dir`](#tymethod.bin_dir) if it belongs to your application alone, or `None` if it is shared with other applications, like the [`Xdg`](struct.Xdg.html) strategy’s `~/.local/bin`.
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets every directory which belongs to your application alone, e.g. for an uninstaller: the configuration, data, cache, state, runtime, log, preference & secret directories, and the binary directory if it isn’t [shared](#method.app_bin_dir).
+    ///
+    /// Directories inside another one of the list are left out, so for the [`Unix`](struct.Unix.html) strategy, this is just `~/.myapp/`. So are the home directory and its parents, in case the environment points a directory at them. The system-wide & temporary directories are never included.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "xdg", feature = "unix"))]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Unix, Xdg};
+    ///
+    /// let strategy_args = AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let app_strategy = Unix::new(strategy_args.clone()).unwrap();
+    /// assert_eq!(app_strategy.all_app_dirs(), vec![app_strategy.config_dir()]);
+    ///
+    /// let app_strategy = Xdg::new(strategy_args).unwrap();
+    /// let all_app_dirs = app_strategy.all_app_dirs();
+    /// assert!(all_app_dirs.contains(&app_strategy.cache_dir()));
+    /// assert!(!all_app_dirs.contains(&app_strategy.bin_dir()));
+    /// # }
+    /// ```
+    fn all_app_dirs(&self) -> Vec<PathBuf> {
+        let candidates: Vec<PathBuf> = [
+            Some(self.config_dir()),
+            Some(self.data_dir()),
+            Some(self.local_data_dir()),
+            Some(self.cache_dir()),
+            self.state_dir(),
+            self.runtime_dir(),
+            self.log_dir(),
+            Some(self.preference_dir()),
+            self.secret_dir(),
+            self.app_bin_dir(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|dir| !crate::is_within(self.home_dir(), dir))
+        .collect();
+
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for (i, dir) in candidates.iter().enumerate() {
+            // Equal directories are only kept the first time around.
+            let is_nested = candidates.iter().enumerate().any(|(j, other)| {
+                j != i && crate::is_within(dir, other) && (dir != other || j < i)
+            });
+            if !is_nested {
+                dirs.push(dir.clone());
+            }
         }
+        dirs
+    }
 
-        /// Returns the current OS’s default [`AppStrategy`](trait.AppStrategy.html).
-        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, and [`Xdg`](struct.Xdg.html) everywhere else.
-        /// This is the convention used by most CLI applications.
-        pub fn choose_app_strategy(args: AppStrategyArgs) -> Result<$app, HomeDirError> {
-            <$app>::new(args)
+    /// Removes every directory of [`all_app_dirs`](#method.all_app_dirs) which exists, including its contents, and returns the ones it removed.
+    /// Symbolic links are removed rather than followed, and the parents of the directories are left alone, even if they end up empty.
+    ///
+    /// ```
+    /// # #[cfg(feature = "unix")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Unix};
+    ///
+    /// let home_dir = std::env::temp_dir().join(format!("etcetera-remove-all-{}", std::process::id()));
+    ///
+    /// let app_strategy = Unix::with_home_dir(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, home_dir.clone()).unwrap();
+    ///
+    /// app_strategy.create_all_dirs().unwrap();
+    /// std::fs::write(app_strategy.in_data_dir("frobnicator.db"), "").unwrap();
+    ///
+    /// assert_eq!(app_strategy.remove_all().unwrap(), vec![app_strategy.config_dir()]);
+    /// assert!(!app_strategy.data_dir().exists());
+    /// assert!(home_dir.is_dir());
+    ///
+    /// // There is nothing left to remove.
+    /// assert!(app_strategy.remove_all().unwrap().is_empty());
+    ///
+    /// std::fs::remove_dir_all(&home_dir).unwrap();
+    /// # }
+    /// ```
+    fn remove_all(&self) -> io::Result<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+        for dir in self.all_app_dirs() {
+            let result = match std::fs::symlink_metadata(&dir) {
+                Ok(metadata) if metadata.is_dir() => std::fs::remove_dir_all(&dir),
+                Ok(_) => std::fs::remove_file(&dir),
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(()) => removed.push(dir),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
         }
-    };
-}
+        Ok(removed)
+    }
 
-cfg_if::cfg_if! {
-    if #[cfg(target_os = "windows")] {
-        create_strategies!(Windows, Windows);
-    } else if #[cfg(any(target_os = "macos", target_os = "ios"))] {
-        create_strategies!(Apple, Xdg);
-    } else {
-        create_strategies!(Xdg, Xdg);
+    /// Gets the system-wide configuration directories for your application, in order of preference.
+    /// These are meant to be searched after [`config_dir`](#tymethod.config_dir), which isn’t included.
+    ///
+    /// For the [`Xdg`](struct.Xdg.html) strategy, your application’s folder is appended to each entry of `XDG_CONFIG_DIRS`, e.g. `/etc/xdg/<app name>`, or of the platform’s defaults outside of Linux & the BSDs. The [`Unix`](struct.Unix.html) strategy has no system-wide directories, so this is empty.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use std::collections::HashMap;
+    /// use std::path::PathBuf;
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::<String, String>::new()).unwrap();
+    ///
+    /// // The XDG defaults are replaced by the platform’s own system-wide folders on macOS, iOS & Windows.
+    /// if cfg!(all(unix, not(any(target_os = "macos", target_os = "ios")))) {
+    ///     assert_eq!(
+    ///         app_strategy.config_dirs(),
+    ///         vec![PathBuf::from("/etc/xdg/frobnicator-plus")]
+    ///     );
+    ///     assert_eq!(
+    ///         app_strategy.data_dirs(),
+    ///         vec![
+    ///             PathBuf::from("/usr/local/share/frobnicator-plus"),
+    ///             PathBuf::from("/usr/share/frobnicator-plus"),
+    ///         ]
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Gets the system-wide data directories for your application, in order of preference.
+    /// These are meant to be searched after [`data_dir`](#tymethod.data_dir), which isn’t included.
+    ///
+    /// For the [`Xdg`](struct.Xdg.html) strategy, your application’s folder is appended to each entry of `XDG_DATA_DIRS`, e.g. `/usr/share/<app name>`. The [`Unix`](struct.Unix.html) strategy has no system-wide directories, so this is empty.
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Gets the single location your application should write its configuration to. This is the same as [`config_dir`](#tymethod.config_dir), and is never one of the system-wide [`config_dirs`](#method.config_dirs).
+    ///
+    /// Together with [`config_read_dirs`](#method.config_read_dirs), this spells out the distinction the XDG specification makes between where to write & where to read from, so that your application doesn’t end up writing into a system-wide directory it merely searched.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::<String, String>::new()).unwrap();
+    ///
+    /// assert_eq!(app_strategy.config_write_dir(), app_strategy.config_dir());
+    ///
+    /// let read_dirs = app_strategy.config_read_dirs();
+    /// assert_eq!(read_dirs[0], app_strategy.config_dir());
+    /// assert_eq!(read_dirs[1..], app_strategy.config_dirs());
+    /// # }
+    /// ```
+    fn config_write_dir(&self) -> PathBuf {
+        self.config_dir()
+    }
+
+    /// Gets every directory your application should read its configuration from, in order of precedence: [`config_dir`](#tymethod.config_dir) first, followed by the system-wide [`config_dirs`](#method.config_dirs).
+    /// See [`config_write_dir`](#method.config_write_dir) for more information.
+    fn config_read_dirs(&self) -> Vec<PathBuf> {
+        std::iter::once(self.config_dir())
+            .chain(self.config_dirs())
+            .collect()
+    }
+
+    /// Gets every directory your application should read its configuration from, like [`config_read_dirs`](#method.config_read_dirs), together with whether it is the user’s own directory or a system-wide one.
+    ///
+    /// The directories are yielded from the highest precedence to the lowest. To layer configuration files like Git does, where later files override earlier ones, load them in reverse:
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use etcetera::ConfigScope;
+    /// use std::collections::HashMap;
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::<String, String>::new()).unwrap();
+    ///
+    /// let mut search_path = app_strategy.config_search_path();
+    /// assert_eq!(search_path.next(), Some((app_strategy.config_dir(), ConfigScope::User)));
+    /// assert!(search_path.all(|(_, scope)| scope == ConfigScope::System));
+    ///
+    /// let mut settings = HashMap::new();
+    /// for (dir, _) in app_strategy.config_search_path().rev() {
+    ///     if let Ok(contents) = std::fs::read_to_string(dir.join("settings.conf")) {
+    ///         for line in contents.lines() {
+    ///             if let Some((key, value)) = line.split_once('=') {
+    ///                 settings.insert(key.to_string(), value.to_string());
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    fn config_search_path(&self) -> std::vec::IntoIter<(PathBuf, ConfigScope)> {
+        std::iter::once((self.config_dir(), ConfigScope::User))
+            .chain(
+                self.config_dirs()
+                    .into_iter()
+                    .map(|dir| (dir, ConfigScope::System)),
+            )
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Gets the single location your application should write its data to. This is the same as [`data_dir`](#tymethod.data_dir), and is never one of the system-wide [`data_dirs`](#method.data_dirs).
+    /// See [`config_write_dir`](#method.config_write_dir) for more information.
+    fn data_write_dir(&self) -> PathBuf {
+        self.data_dir()
+    }
+
+    /// Gets every directory your application should read its data from, in order of precedence: [`data_dir`](#tymethod.data_dir) first, followed by the system-wide [`data_dirs`](#method.data_dirs).
+    /// See [`config_write_dir`](#method.config_write_dir) for more information.
+    fn data_read_dirs(&self) -> Vec<PathBuf> {
+        std::iter::once(self.data_dir())
+            .chain(self.data_dirs())
+            .collect()
+    }
+
+    #[cfg(feature = "camino")]
+    utf8_methods!(
+        config_dir_utf8 => config_dir,
+        data_dir_utf8 => data_dir,
+        cache_dir_utf8 => cache_dir,
+        state_dir_utf8 => state_dir,
+        runtime_dir_utf8 => runtime_dir,
+        log_dir_utf8 => log_dir,
+        bin_dir_utf8 => bin_dir,
+    );
+
+    /// Returns whether `path` lies inside the user’s home directory.
+    /// See [`BaseStrategy::is_within_home`](../base_strategy/trait.BaseStrategy.html#method.is_within_home) for more information.
+    fn is_within_home(&self, path: &Path) -> bool {
+        crate::is_within(path, self.home_dir())
+    }
+
+    /// Abbreviates `path` for display by replacing the user’s home directory with `~`, e.g. `~/.config/`. See [`tilde_abbreviate`](../fn.tilde_abbreviate.html) for more information.
+    fn tilde_abbreviate(&self, path: &Path) -> String {
+        crate::tilde_abbreviate(path, self.home_dir())
+    }
+
+    /// Gets [`config_dir`](#tymethod.config_dir) without allocating if the strategy already stores it, like [`Cached`](../struct.Cached.html) does once the directory has been computed. Otherwise, an owned path is returned.
+    fn config_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Owned(self.config_dir())
+    }
+
+    /// Gets [`data_dir`](#tymethod.data_dir), borrowing it if possible. See [`config_dir_cow`](#method.config_dir_cow) for more information.
+    fn data_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Owned(self.data_dir())
+    }
+
+    /// Gets [`cache_dir`](#tymethod.cache_dir), borrowing it if possible. See [`config_dir_cow`](#method.config_dir_cow) for more information.
+    fn cache_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Owned(self.cache_dir())
+    }
+
+    /// Resolves every directory of your application at once.
+    ///
+    /// ```
+    /// # #[cfg(feature = "unix")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Unix};
+    ///
+    /// let home_path = if cfg!(windows) { "C:\\home\\" } else { "/home/" };
+    ///
+    /// let app_strategy = Unix::with_home_dir(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, home_path.into()).unwrap();
+    ///
+    /// let snapshot = app_strategy.snapshot();
+    /// assert_eq!(snapshot.config_dir, app_strategy.config_dir());
+    /// assert_eq!(snapshot.log_dir, app_strategy.log_dir());
+    /// # }
+    /// ```
+    fn snapshot(&self) -> ResolvedDirs {
+        ResolvedDirs {
+            home_dir: self.home_dir().to_path_buf(),
+            config_dir: self.config_dir(),
+            data_dir: self.data_dir(),
+            cache_dir: self.cache_dir(),
+            state_dir: self.state_dir(),
+            runtime_dir: self.runtime_dir(),
+            log_dir: self.log_dir(),
+            bin_dir: self.bin_dir(),
+        }
+    }
+
+    /// Finds out which of your application’s directories `path` lies in, e.g. to tell whether a file the user passed in is a cache entry.
+    /// This is the same as `self.snapshot().classify(path)`; see [`ResolvedDirs::classify`](../struct.ResolvedDirs.html#method.classify) for more information.
+    fn classify(&self, path: &Path) -> Option<DirClassification> {
+        self.snapshot().classify(path)
+    }
+
+    /// Gets the configuration directory for your application with its symlinks resolved. See [`canonicalize_existing`](../fn.canonicalize_existing.html) for more information.
+    fn canonical_config_dir(&self) -> io::Result<PathBuf> {
+        crate::canonicalize_existing(self.config_dir())
+    }
+
+    /// Gets the data directory for your application with its symlinks resolved. See [`canonicalize_existing`](../fn.canonicalize_existing.html) for more information.
+    fn canonical_data_dir(&self) -> io::Result<PathBuf> {
+        crate::canonicalize_existing(self.data_dir())
+    }
+
+    /// Gets the cache directory for your application with its symlinks resolved. See [`canonicalize_existing`](../fn.canonicalize_existing.html) for more information.
+    fn canonical_cache_dir(&self) -> io::Result<PathBuf> {
+        crate::canonicalize_existing(self.cache_dir())
+    }
+
+    /// Checks whether your application’s configuration directory exists, without creating it.
+    /// See [`BaseStrategy::config_dir_exists`](../base_strategy/trait.BaseStrategy.html#method.config_dir_exists) for more information.
+    fn config_dir_exists(&self) -> io::Result<bool> {
+        self.config_dir().try_exists()
+    }
+
+    /// Checks whether your application’s data directory exists, without creating it. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn data_dir_exists(&self) -> io::Result<bool> {
+        self.data_dir().try_exists()
+    }
+
+    /// Checks whether your application’s cache directory exists, without creating it. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn cache_dir_exists(&self) -> io::Result<bool> {
+        self.cache_dir().try_exists()
+    }
+
+    /// Checks whether your application’s state directory exists, without creating it. This returns `false` if there is no state directory at all. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn state_dir_exists(&self) -> io::Result<bool> {
+        self.state_dir().map_or(Ok(false), |dir| dir.try_exists())
+    }
+
+    /// Checks whether your application’s runtime directory exists, without creating it. This returns `false` if there is no runtime directory at all. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn runtime_dir_exists(&self) -> io::Result<bool> {
+        self.runtime_dir().map_or(Ok(false), |dir| dir.try_exists())
+    }
+
+    /// Returns whether this is the first time your application runs for the current user, e.g. to decide whether to show an onboarding flow.
+    ///
+    /// This is exactly the case when neither the [`config_dir`](#tymethod.config_dir) nor the [`data_dir`](#tymethod.data_dir) exists. Directories whose existence can’t be determined, e.g. because a parent directory isn’t readable, count as existing, so that a locked-down profile doesn’t trigger the first-time setup on every start. Call [`mark_initialized`](#method.mark_initialized) once the setup is done.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-is-first-run-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([
+    ///     ("XDG_CONFIG_HOME", root.join("config")),
+    ///     ("XDG_DATA_HOME", root.join("data")),
+    /// ])).unwrap();
+    ///
+    /// assert!(app_strategy.is_first_run());
+    ///
+    /// app_strategy.mark_initialized().unwrap();
+    /// assert!(app_strategy.in_data_dir(".initialized").is_file());
+    /// assert!(!app_strategy.is_first_run());
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn is_first_run(&self) -> bool {
+        let exists = |dir: PathBuf| dir.try_exists().unwrap_or(true);
+        !exists(self.config_dir()) && !exists(self.data_dir())
+    }
+
+    /// Records that your application has been set up for the current user, by creating an empty `.initialized` file in its [`data_dir`](#tymethod.data_dir), along with the directory itself. [`is_first_run`](#method.is_first_run) returns `false` from then on.
+    /// Calling this again leaves the existing file untouched.
+    fn mark_initialized(&self) -> io::Result<()> {
+        let data_dir = self.create_data_dir()?;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(data_dir.join(".initialized"))?;
+        Ok(())
+    }
+
+    /// Constructs a path inside your application’s configuration directory to which a path of your choice has been appended.
+    fn in_config_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
+        in_dir_method!(self, path, config_dir)
+    }
+
+    /// Constructs a path inside your application’s data directory to which a path of your choice has been appended.
+    fn in_data_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
+        in_dir_method!(self, path, data_dir)
+    }
+
+    /// Constructs a path inside your application’s cache directory to which a path of your choice has been appended.
+    fn in_cache_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
+        in_dir_method!(self, path, cache_dir)
+    }
+
+    /// Constructs a path inside your application’s state directory to which a path of your choice has been appended.
+    /// Currently, only the [`Xdg`](struct.Xdg.html), [`Unix`](struct.Unix.html), [`Windows`](struct.Windows.html) & [`Apple`](struct.Apple.html) strategies support
+    /// this.
+    fn in_state_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
+        in_dir_method!(opt: self, path, state_dir)
+    }
+
+    /// Constructs a path inside your application’s runtime directory to which a path of your choice has been appended.
+    /// Currently, only the [`Xdg`](struct.Xdg.html), [`Unix`](struct.Unix.html) & [`Windows`](struct.Windows.html) strategies support
+    /// this.
+    ///
+    /// See the note in [`runtime_dir`](#method.runtime_dir) for more information.
+    fn in_runtime_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
+        in_dir_method!(opt: self, path, runtime_dir)
+    }
+
+    /// Constructs a path inside your application’s log directory to which a path of your choice has been appended.
+    fn in_log_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
+        in_dir_method!(opt: self, path, log_dir)
+    }
+
+    /// Constructs a path inside your application’s binary directory to which a path of your choice has been appended.
+    fn in_bin_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
+        in_dir_method!(self, path, bin_dir)
+    }
+
+    /// Constructs the path of a file inside your application’s configuration directory, like [`in_config_dir`](#method.in_config_dir), and creates its parent directories, so that the file is ready to be written.
+    /// A `path` with several components, e.g. `themes/dark.toml`, creates the folders in between too. The file itself isn’t created.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-config-file-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_CONFIG_HOME", root.clone())])).unwrap();
+    ///
+    /// let theme_file = app_strategy.config_file("themes/dark.toml").unwrap();
+    /// assert_eq!(theme_file, app_strategy.config_dir().join("themes/dark.toml"));
+    /// assert!(app_strategy.config_dir().join("themes").is_dir());
+    /// assert!(!theme_file.exists());
+    ///
+    /// std::fs::write(&theme_file, "background = \"black\"").unwrap();
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn config_file<P: AsRef<OsStr>>(&self, path: P) -> io::Result<PathBuf> {
+        file_method!(self, path, config_dir)
+    }
+
+    /// Constructs the path of a file inside your application’s data directory, and creates its parent directories.
+    /// See [`config_file`](#method.config_file) for more information.
+    fn data_file<P: AsRef<OsStr>>(&self, path: P) -> io::Result<PathBuf> {
+        file_method!(self, path, data_dir)
+    }
+
+    /// Constructs the path of a file inside your application’s state directory, and creates its parent directories.
+    /// See [`config_file`](#method.config_file) for more information.
+    ///
+    /// The state directory is meant for data which should persist between runs but isn’t worth backing up or syncing, such as a command history, a journal of recent actions, or the layout of your application’s windows. Unlike with [`first_writable_config_dir`](#method.first_writable_config_dir), there is no fallback: this fails with [`io::ErrorKind::NotFound`] if the strategy has no state directory.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    /// use std::io::Write;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-state-file-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_STATE_HOME", root.clone())])).unwrap();
+    ///
+    /// let history_file = app_strategy.state_file("history").unwrap();
+    /// for command in ["frobnicate", "unfrobnicate"] {
+    ///     let mut history = std::fs::OpenOptions::new().create(true).append(true).open(&history_file).unwrap();
+    ///     writeln!(history, "{command}").unwrap();
+    /// }
+    /// assert_eq!(std::fs::read_to_string(&history_file).unwrap(), "frobnicate\nunfrobnicate\n");
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn state_file<P: AsRef<OsStr>>(&self, path: P) -> io::Result<PathBuf> {
+        file_method!(
+            opt: self,
+            path,
+            state_dir,
+            "the strategy has no state directory"
+        )
+    }
+
+    /// Creates your application’s configuration directory (and any missing parents) and returns its path.
+    fn create_config_dir(&self) -> io::Result<PathBuf> {
+        self.create_config_dir_in(&StdFs)
+    }
+
+    /// Creates your application’s data directory (and any missing parents) and returns its path.
+    fn create_data_dir(&self) -> io::Result<PathBuf> {
+        self.create_data_dir_in(&StdFs)
+    }
+
+    /// Creates your application’s cache directory (and any missing parents) and returns its path.
+    fn create_cache_dir(&self) -> io::Result<PathBuf> {
+        self.create_cache_dir_in(&StdFs)
+    }
+
+    /// Creates your application’s state directory (and any missing parents) and returns its path.
+    /// On Unix, it is only accessible to its owner, see [`fs::PRIVATE_DIR_MODE`](../fs/constant.PRIVATE_DIR_MODE.html).
+    ///
+    /// Returns `Ok(None)` without touching the filesystem if the strategy has no state directory.
+    fn create_state_dir(&self) -> io::Result<Option<PathBuf>> {
+        self.create_state_dir_in(&StdFs)
+    }
+
+    /// Creates your application’s runtime directory (and any missing parents) and returns its path.
+    /// On Unix, it is only accessible to its owner, see [`fs::PRIVATE_DIR_MODE`](../fs/constant.PRIVATE_DIR_MODE.html).
+    ///
+    /// Returns `Ok(None)` without touching the filesystem if the strategy has no runtime directory.
+    /// See the note in [`runtime_dir`](#method.runtime_dir) for more information.
+    fn create_runtime_dir(&self) -> io::Result<Option<PathBuf>> {
+        self.create_runtime_dir_in(&StdFs)
+    }
+
+    /// Creates your application’s [secret directory](#method.secret_dir) (and any missing parents) and returns its path, or `Ok(None)` if the strategy has none.
+    ///
+    /// On Unix, the directory is only accessible to its owner afterwards, even if it already existed with looser permissions.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-create-secret-dir-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_STATE_HOME", root.clone())])).unwrap();
+    ///
+    /// let secret_dir = app_strategy.create_secret_dir().unwrap().unwrap();
+    /// assert_eq!(secret_dir, root.join("frobnicator-plus/secrets/"));
+    ///
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::os::unix::fs::PermissionsExt;
+    ///
+    /// std::fs::set_permissions(&secret_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+    /// app_strategy.create_secret_dir().unwrap();
+    /// assert_eq!(secret_dir.metadata().unwrap().permissions().mode() & 0o777, 0o700);
+    /// # }
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn create_secret_dir(&self) -> io::Result<Option<PathBuf>> {
+        let Some(path) = self.secret_dir() else {
+            return Ok(None);
+        };
+        StdFs.create_dir_all_with_mode(&path, PRIVATE_DIR_MODE)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let permissions = std::fs::metadata(&path)?.permissions();
+            if permissions.mode() & 0o077 != 0 {
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(PRIVATE_DIR_MODE))?;
+            }
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Creates your application’s configuration, data, cache & state directories in one go.
+    ///
+    /// On Unix, the state directory is only accessible to its owner, while the others are readable by everyone, see [`fs::DEFAULT_DIR_MODE`](../fs/constant.DEFAULT_DIR_MODE.html). Windows folders inherit their permissions from their parent instead.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-create-all-dirs-{}", std::process::id()));
+    /// std::env::set_var("XDG_CONFIG_HOME", root.join("config"));
+    /// std::env::set_var("XDG_DATA_HOME", root.join("data"));
+    /// std::env::set_var("XDG_CACHE_HOME", root.join("cache"));
+    /// std::env::set_var("XDG_STATE_HOME", root.join("state"));
+    ///
+    /// let app_strategy = Xdg::new(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }).unwrap();
+    ///
+    /// app_strategy.create_all_dirs().unwrap();
+    ///
+    /// assert!(app_strategy.config_dir().is_dir());
+    /// assert!(app_strategy.data_dir().is_dir());
+    /// assert!(app_strategy.cache_dir().is_dir());
+    /// assert!(app_strategy.state_dir().unwrap().is_dir());
+    ///
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::os::unix::fs::PermissionsExt;
+    ///
+    /// let mode = |path: std::path::PathBuf| path.metadata().unwrap().permissions().mode() & 0o777;
+    /// assert_eq!(mode(app_strategy.state_dir().unwrap()), 0o700);
+    /// // The umask may take away more permissions, but never adds any.
+    /// assert_eq!(mode(app_strategy.config_dir()) & !0o755, 0);
+    /// # }
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn create_all_dirs(&self) -> io::Result<()> {
+        self.create_all_dirs_in(&StdFs)
+    }
+
+    /// Returns the first directory your application can write its configuration to, creating it if need be. See [`first_writable_dir`](../fn.first_writable_dir.html) for how each directory is probed.
+    ///
+    /// This tries [`config_dir`](#tymethod.config_dir) first, then [`local_data_dir`](#method.local_data_dir) & [`state_dir`](#tymethod.state_dir), which are more likely to be writable if the configuration directory is locked down, e.g. by a read-only roaming profile. The system-wide [`config_dirs`](#method.config_dirs) are never tried.
+    fn first_writable_config_dir(&self) -> io::Result<PathBuf> {
+        let mut candidates = vec![self.config_dir(), self.local_data_dir()];
+        candidates.extend(self.state_dir());
+        crate::first_writable_dir(candidates)
+    }
+
+    /// Creates your application’s configuration directory through `fs` and returns its path.
+    /// See the [`fs`](../fs/index.html) module for more information.
+    fn create_config_dir_in<F: FileSystem + ?Sized>(&self, fs: &F) -> io::Result<PathBuf> {
+        create_dir_method!(self, fs, config_dir, DEFAULT_DIR_MODE)
+    }
+
+    /// Creates your application’s data directory through `fs` and returns its path.
+    /// See the [`fs`](../fs/index.html) module for more information.
+    fn create_data_dir_in<F: FileSystem + ?Sized>(&self, fs: &F) -> io::Result<PathBuf> {
+        create_dir_method!(self, fs, data_dir, DEFAULT_DIR_MODE)
+    }
+
+    /// Creates your application’s cache directory through `fs` and returns its path.
+    /// See the [`fs`](../fs/index.html) module for more information.
+    fn create_cache_dir_in<F: FileSystem + ?Sized>(&self, fs: &F) -> io::Result<PathBuf> {
+        create_dir_method!(self, fs, cache_dir, DEFAULT_DIR_MODE)
+    }
+
+    /// Creates your application’s state directory through `fs` and returns its path, or `Ok(None)` if the strategy has no state directory.
+    /// See the [`fs`](../fs/index.html) module for more information.
+    fn create_state_dir_in<F: FileSystem + ?Sized>(&self, fs: &F) -> io::Result<Option<PathBuf>> {
+        create_dir_method!(opt: self, fs, state_dir, PRIVATE_DIR_MODE)
+    }
+
+    /// Creates your application’s runtime directory through `fs` and returns its path, or `Ok(None)` if the strategy has no runtime directory.
+    /// See the [`fs`](../fs/index.html) module for more information.
+    fn create_runtime_dir_in<F: FileSystem + ?Sized>(&self, fs: &F) -> io::Result<Option<PathBuf>> {
+        create_dir_method!(opt: self, fs, runtime_dir, PRIVATE_DIR_MODE)
+    }
+
+    /// Creates your application’s configuration, data, cache & state directories through `fs` in one go.
+    /// See the [`fs`](../fs/index.html) module for more information.
+    fn create_all_dirs_in<F: FileSystem + ?Sized>(&self, fs: &F) -> io::Result<()> {
+        self.create_config_dir_in(fs)?;
+        self.create_data_dir_in(fs)?;
+        self.create_cache_dir_in(fs)?;
+        self.create_state_dir_in(fs)?;
+        Ok(())
+    }
+
+    /// Lists the directories [`create_all_dirs`](#method.create_all_dirs) would create, in order, including missing parents, without creating anything, e.g. to ask for confirmation first or to audit the plan in a test.
+    /// This runs `create_all_dirs_in` on a [`DryRunFs`](../fs/struct.DryRunFs.html), so the plan can’t drift away from what actually happens.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-plan-create-{}", std::process::id()));
+    /// std::fs::create_dir_all(root.join("config")).unwrap();
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([
+    ///     ("XDG_CONFIG_HOME", root.join("config")),
+    ///     ("XDG_DATA_HOME", root.join("data")),
+    ///     ("XDG_CACHE_HOME", root.join("cache")),
+    ///     ("XDG_STATE_HOME", root.join("state")),
+    /// ])).unwrap();
+    ///
+    /// assert_eq!(
+    ///     app_strategy.plan_create(),
+    ///     vec![
+    ///         root.join("config/frobnicator-plus"),
+    ///         root.join("data"),
+    ///         root.join("data/frobnicator-plus"),
+    ///         root.join("cache"),
+    ///         root.join("cache/frobnicator-plus"),
+    ///         root.join("state"),
+    ///         root.join("state/frobnicator-plus"),
+    ///     ]
+    /// );
+    /// assert!(!root.join("data").exists());
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn plan_create(&self) -> Vec<PathBuf> {
+        self.plan_create_in(&StdFs)
+    }
+
+    /// Lists the directories [`create_all_dirs_in`](#method.create_all_dirs_in) would create through `fs`, without creating anything.
+    /// See [`plan_create`](#method.plan_create) for more information.
+    fn plan_create_in<F: FileSystem + ?Sized>(&self, fs: &F) -> Vec<PathBuf> {
+        let dry_run = DryRunFs::new(fs);
+        // `DryRunFs` never fails, as it doesn’t touch anything.
+        let _ = self.create_all_dirs_in(&dry_run);
+        dry_run.planned()
+    }
+
+    /// Moves your application’s configuration, data & state from the directories of another strategy to this one’s, e.g. when switching from the [`Unix`](struct.Unix.html) layout to the [`Xdg`](struct.Xdg.html) one.
+    ///
+    /// Each file & folder inside the old directories is moved on its own, and is skipped if its destination already exists, so this is safe to call on every start-up. Directories of the old strategy which are nested inside each other are migrated separately.
+    ///
+    /// When the old & new directories are on different filesystems, entries are copied & then removed; symbolic links are copied as links, & an entry only appears at its destination once it has been copied completely.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "xdg", feature = "unix"))]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Unix, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let home_dir = std::env::temp_dir().join(format!("etcetera-migrate-from-{}", std::process::id()));
+    /// let args = AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let old = Unix::with_home_dir(args.clone(), home_dir.clone()).unwrap();
+    /// let new = Xdg::with_home_dir(args, home_dir.clone())
+    ///     .unwrap()
+    ///     .with_env(HashMap::<String, String>::new());
+    ///
+    /// std::fs::create_dir_all(old.data_dir()).unwrap();
+    /// std::fs::write(old.in_config_dir("config.toml"), "").unwrap();
+    /// std::fs::write(old.in_data_dir("db.sqlite"), "").unwrap();
+    ///
+    /// let report = new.migrate_from(&old).unwrap();
+    ///
+    /// assert_eq!(report.moved.len(), 2);
+    /// assert!(new.in_config_dir("config.toml").is_file());
+    /// assert!(new.in_data_dir("db.sqlite").is_file());
+    /// assert!(!old.in_config_dir("config.toml").exists());
+    ///
+    /// // Existing files are never overwritten.
+    /// std::fs::write(old.in_config_dir("config.toml"), "").unwrap();
+    /// let report = new.migrate_from(&old).unwrap();
+    ///
+    /// assert!(report.moved.is_empty());
+    /// assert_eq!(
+    ///     report.skipped,
+    ///     vec![(old.in_config_dir("config.toml"), new.in_config_dir("config.toml"))]
+    /// );
+    ///
+    /// std::fs::remove_dir_all(&home_dir).unwrap();
+    /// # }
+    /// ```
+    fn migrate_from(&self, old: &impl AppStrategy) -> io::Result<MigrationReport> {
+        migration::migrate(self, old)
+    }
+
+    /// Removes everything inside your application’s cache directory, but not the directory itself, e.g. for a “clear cache” button.
+    ///
+    /// Symlinks inside the cache directory are removed rather than followed, so nothing outside of it is ever deleted. The `CACHEDIR.TAG` written by [`mark_cache_dir`](#method.mark_cache_dir) is kept. If the directory doesn’t exist, this does nothing.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-clear-cache-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_CACHE_HOME", root.clone())])).unwrap();
+    ///
+    /// assert_eq!(app_strategy.cache_size().unwrap(), 0);
+    /// app_strategy.clear_cache().unwrap();
+    ///
+    /// let cache_dir = app_strategy.cache_dir();
+    /// std::fs::create_dir_all(cache_dir.join("thumbnails")).unwrap();
+    /// std::fs::write(cache_dir.join("index"), [0; 3]).unwrap();
+    /// std::fs::write(cache_dir.join("thumbnails/1.png"), [0; 4]).unwrap();
+    ///
+    /// assert_eq!(app_strategy.cache_size().unwrap(), 7);
+    ///
+    /// app_strategy.clear_cache().unwrap();
+    /// assert!(cache_dir.is_dir());
+    /// assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 0);
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn clear_cache(&self) -> io::Result<()> {
+        cache::clear(&self.cache_dir())
+    }
+
+    /// Marks your application’s cache directory as such by writing a `CACHEDIR.TAG` file into it, as described by the [Cache Directory Tagging Specification](https://bford.info/cachedir/). Backup tools such as Borg, restic or `tar --exclude-caches` then skip the directory.
+    ///
+    /// The directory is created if it doesn’t exist yet, and a valid tag is left untouched, so this is safe to call on every start-up.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-mark-cache-dir-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_CACHE_HOME", root.clone())])).unwrap();
+    ///
+    /// app_strategy.mark_cache_dir().unwrap();
+    /// app_strategy.mark_cache_dir().unwrap();
+    ///
+    /// let tag = std::fs::read_to_string(app_strategy.in_cache_dir("CACHEDIR.TAG")).unwrap();
+    /// assert!(tag.starts_with("Signature: 8a477f597d28d172789f06886806bc55"));
+    ///
+    /// // Clearing the cache keeps the tag.
+    /// app_strategy.clear_cache().unwrap();
+    /// assert!(app_strategy.in_cache_dir("CACHEDIR.TAG").is_file());
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn mark_cache_dir(&self) -> io::Result<()> {
+        cache::mark(&self.cache_dir())
+    }
+
+    /// Sums up the sizes of the files inside your application’s cache directory, in bytes.
+    ///
+    /// Symlinks aren’t followed, so files outside of the cache directory aren’t counted. If the directory doesn’t exist, this returns 0.
+    fn cache_size(&self) -> io::Result<u64> {
+        cache::size(&self.cache_dir())
+    }
+
+    /// Takes an exclusive lock on the file `<name>.lock` in your application’s runtime directory, creating both if need be, e.g. to make sure only one instance of your application runs at a time. The lock is held until the returned [`LockGuard`](struct.LockGuard.html) is dropped.
+    ///
+    /// If your application has no runtime directory, the lock file goes into its state directory instead. If it has neither, this returns an error of kind `NotFound`. If another process holds the lock, this doesn’t wait for it, but returns an error of kind `WouldBlock`.
+    ///
+    /// The lock is taken with `flock` on Unix & `LockFileEx` on Windows, so the operating system releases it when the process exits, even if it crashed. Lock files left behind are therefore never stale, and are reused by the next call.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    /// use std::io;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-acquire-lock-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_RUNTIME_DIR", root.clone())])).unwrap();
+    ///
+    /// let lock = app_strategy.acquire_lock("instance").unwrap();
+    /// assert_eq!(lock.path(), root.join("frobnicator-plus/instance.lock"));
+    /// assert_eq!(
+    ///     app_strategy.acquire_lock("instance").unwrap_err().kind(),
+    ///     io::ErrorKind::WouldBlock
+    /// );
+    ///
+    /// drop(lock);
+    /// assert!(app_strategy.acquire_lock("instance").is_ok());
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn acquire_lock(&self, name: &str) -> io::Result<LockGuard> {
+        let dir = self
+            .runtime_dir()
+            .or_else(|| self.state_dir())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "there is neither a runtime nor a state directory to put the lock file in",
+                )
+            })?;
+        lock::acquire(&dir, name)
+    }
+
+    /// Looks for a file in your application’s configuration directory first, and then in each of the [`config_dirs`](#method.config_dirs), returning the first one that exists.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-find-config-file-{}", std::process::id()));
+    /// let user = root.join("user");
+    /// let system = root.join("system");
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([
+    ///     ("XDG_CONFIG_HOME", user.clone()),
+    ///     ("XDG_CONFIG_DIRS", system.clone()),
+    /// ])).unwrap();
+    ///
+    /// for dir in [&user, &system] {
+    ///     std::fs::create_dir_all(dir.join("frobnicator-plus")).unwrap();
+    ///     std::fs::write(dir.join("frobnicator-plus/config.toml"), "").unwrap();
+    /// }
+    /// std::fs::write(system.join("frobnicator-plus/defaults.toml"), "").unwrap();
+    ///
+    /// // The user’s configuration takes precedence over the system-wide one.
+    /// assert_eq!(
+    ///     app_strategy.find_config_file("config.toml"),
+    ///     Some(user.join("frobnicator-plus/config.toml"))
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.find_all_config_files("config.toml"),
+    ///     vec![
+    ///         user.join("frobnicator-plus/config.toml"),
+    ///         system.join("frobnicator-plus/config.toml"),
+    ///     ]
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.find_config_file("defaults.toml"),
+    ///     Some(system.join("frobnicator-plus/defaults.toml"))
+    /// );
+    /// assert_eq!(app_strategy.find_config_file("missing.toml"), None);
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn find_config_file<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
+        self.config_read_dirs()
+            .into_iter()
+            .map(|config_dir| config_dir.join(path.as_ref()))
+            .find(|path| path.is_file())
+    }
+
+    /// Looks for a file in your application’s configuration directory and in each of the [`config_dirs`](#method.config_dirs), returning every one that exists.
+    /// The files are in order of precedence, i.e. the user’s one comes first, which is useful for merging layered configuration.
+    fn find_all_config_files<P: AsRef<OsStr>>(&self, path: P) -> Vec<PathBuf> {
+        self.config_read_dirs()
+            .into_iter()
+            .map(|config_dir| config_dir.join(path.as_ref()))
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    /// Lists the files with the given extension in your application’s configuration directory, sorted by name, e.g. to load every `*.toml` file in it.
+    ///
+    /// The extension may be given with or without its leading dot, and is compared case-insensitively by default, as `config.TOML` is still a TOML file; use [`list_config_files_matching`](#method.list_config_files_matching) to compare it case-sensitively instead. Subfolders aren’t listed, even if their name matches, and neither are their contents. If the directory doesn’t exist, this returns an empty list.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-list-config-files-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_CONFIG_HOME", root.clone())])).unwrap();
+    ///
+    /// assert!(app_strategy.list_config_files("toml").unwrap().is_empty());
+    ///
+    /// let config_dir = app_strategy.config_dir();
+    /// std::fs::create_dir_all(config_dir.join("nested.toml")).unwrap();
+    /// for file in ["b.toml", "A.TOML", "c.json", "toml"] {
+    ///     std::fs::write(config_dir.join(file), "").unwrap();
+    /// }
+    ///
+    /// assert_eq!(
+    ///     app_strategy.list_config_files(".toml").unwrap(),
+    ///     vec![config_dir.join("A.TOML"), config_dir.join("b.toml")]
+    /// );
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn list_config_files(&self, extension: &str) -> io::Result<Vec<PathBuf>> {
+        self.list_config_files_matching(extension, false)
+    }
+
+    /// Like [`list_config_files`](#method.list_config_files), but the extension is only compared case-sensitively if `case_sensitive` is true, e.g. when `.Json` files are meant to be ignored.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-list-config-files-matching-{}", std::process::id()));
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_CONFIG_HOME", root.clone())])).unwrap();
+    ///
+    /// let config_dir = app_strategy.config_dir();
+    /// std::fs::create_dir_all(&config_dir).unwrap();
+    /// for file in ["a.json", "B.JSON"] {
+    ///     std::fs::write(config_dir.join(file), "").unwrap();
+    /// }
+    ///
+    /// assert_eq!(
+    ///     app_strategy.list_config_files_matching("json", true).unwrap(),
+    ///     vec![config_dir.join("a.json")]
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.list_config_files_matching("json", false).unwrap(),
+    ///     app_strategy.list_config_files("json").unwrap()
+    /// );
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn list_config_files_matching(
+        &self,
+        extension: &str,
+        case_sensitive: bool,
+    ) -> io::Result<Vec<PathBuf>> {
+        crate::list_files(&self.config_dir(), extension, case_sensitive)
+    }
+
+    /// Lists the files with the given extension in your application’s data directory, sorted by name.
+    /// See [`list_config_files`](#method.list_config_files) for more information.
+    fn list_data_files(&self, extension: &str) -> io::Result<Vec<PathBuf>> {
+        self.list_data_files_matching(extension, false)
+    }
+
+    /// Like [`list_data_files`](#method.list_data_files), but the extension is only compared case-sensitively if `case_sensitive` is true.
+    fn list_data_files_matching(
+        &self,
+        extension: &str,
+        case_sensitive: bool,
+    ) -> io::Result<Vec<PathBuf>> {
+        crate::list_files(&self.data_dir(), extension, case_sensitive)
+    }
+
+    /// Lists the files with the given extension in your application’s cache directory, sorted by name.
+    /// See [`list_config_files`](#method.list_config_files) for more information.
+    fn list_cache_files(&self, extension: &str) -> io::Result<Vec<PathBuf>> {
+        self.list_cache_files_matching(extension, false)
+    }
+
+    /// Like [`list_cache_files`](#method.list_cache_files), but the extension is only compared case-sensitively if `case_sensitive` is true.
+    fn list_cache_files_matching(
+        &self,
+        extension: &str,
+        case_sensitive: bool,
+    ) -> io::Result<Vec<PathBuf>> {
+        crate::list_files(&self.cache_dir(), extension, case_sensitive)
+    }
+
+    /// Looks for a file or folder in your application’s configuration or data directory, then in each of its system-wide counterparts, i.e. [`config_dirs`](#method.config_dirs) or [`data_dirs`](#method.data_dirs), returning the first one that exists.
+    /// The other kinds of directories have no system-wide counterparts, so only your application’s own directory is searched, if it has one.
+    /// Unlike [`find_config_file`](#method.find_config_file), this also finds folders, e.g. for theme or plugin discovery.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use etcetera::DirKind;
+    /// use std::collections::HashMap;
+    ///
+    /// let root = std::env::temp_dir().join(format!("etcetera-find-in-dirs-{}", std::process::id()));
+    /// let user = root.join("user");
+    /// let system = root.join("system");
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([
+    ///     ("XDG_DATA_HOME", user.clone()),
+    ///     ("XDG_DATA_DIRS", system.clone()),
+    /// ])).unwrap();
+    ///
+    /// std::fs::create_dir_all(user.join("frobnicator-plus/themes/dark")).unwrap();
+    /// std::fs::create_dir_all(system.join("frobnicator-plus/themes/dark")).unwrap();
+    /// std::fs::create_dir_all(system.join("frobnicator-plus/themes/light")).unwrap();
+    ///
+    /// assert_eq!(
+    ///     app_strategy.find_in_dirs(DirKind::Data, "themes/dark"),
+    ///     Some(user.join("frobnicator-plus/themes/dark"))
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.find_in_dirs(DirKind::Data, "themes/light"),
+    ///     Some(system.join("frobnicator-plus/themes/light"))
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.find_all_in_dirs(DirKind::Data, "themes/dark").collect::<Vec<_>>(),
+    ///     vec![
+    ///         user.join("frobnicator-plus/themes/dark"),
+    ///         system.join("frobnicator-plus/themes/dark"),
+    ///     ]
+    /// );
+    /// assert_eq!(app_strategy.find_in_dirs(DirKind::Config, "themes/dark"), None);
+    ///
+    /// std::fs::remove_dir_all(&root).unwrap();
+    /// # }
+    /// ```
+    fn find_in_dirs<P: AsRef<Path>>(&self, kind: DirKind, path: P) -> Option<PathBuf> {
+        self.find_all_in_dirs(kind, path).next()
+    }
+
+    /// Looks for a file or folder like [`find_in_dirs`](#method.find_in_dirs), but lazily yields every one that exists, in order of precedence.
+    fn find_all_in_dirs<P: AsRef<Path>>(&self, kind: DirKind, path: P) -> FindInDirs {
+        let dirs = match kind {
+            DirKind::Config => self.config_read_dirs(),
+            DirKind::Data => self.data_read_dirs(),
+            kind => self.dir(kind).into_iter().collect(),
+        };
+
+        FindInDirs {
+            dirs: dirs.into_iter(),
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+/// An iterator over the files & folders found by [`AppStrategy::find_all_in_dirs`](trait.AppStrategy.html#method.find_all_in_dirs).
+#[derive(Debug, Clone)]
+pub struct FindInDirs {
+    dirs: std::vec::IntoIter<PathBuf>,
+    path: PathBuf,
+}
+
+impl Iterator for FindInDirs {
+    type Item = PathBuf;
+
+    fn next(&mut self) -> Option<PathBuf> {
+        self.dirs
+            .by_ref()
+            .map(|dir| dir.join(&self.path))
+            .find(|path| path.exists())
+    }
+}
+
+macro_rules! create_strategies {
+    ($native: ident, $app: ident) => {
+        /// Returns the current OS’s native [`AppStrategy`](trait.AppStrategy.html).
+        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, [`Apple`](struct.Apple.html) on macOS & iOS, [`InMemory`](struct.InMemory.html) on `wasm32-unknown-unknown`, and [`Xdg`](struct.Xdg.html) everywhere else.
+        /// This is the convention used by most GUI applications.
+        pub fn choose_native_strategy(args: AppStrategyArgs) -> Result<$native, CreationError> {
+            create_strategies!(@new $native, args)
+        }
+
+        /// Returns the current OS’s default [`AppStrategy`](trait.AppStrategy.html).
+        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, [`InMemory`](struct.InMemory.html) on `wasm32-unknown-unknown`, and [`Xdg`](struct.Xdg.html) everywhere else.
+        /// This is the convention used by most CLI applications.
+        pub fn choose_app_strategy(args: AppStrategyArgs) -> Result<$app, CreationError> {
+            create_strategies!(@new $app, args)
+        }
+    };
+    // See `base_strategy::choose_base_strategy`.
+    (@new InMemory, $args: ident) => {
+        if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+            InMemory::new($args)
+        } else {
+            crate::home_dir()
+                .map_err(CreationError::from)
+                .and_then(|home_dir| InMemory::with_root_dir($args, home_dir))
+        }
+    };
+    (@new $strategy: ident, $args: ident) => {
+        $strategy::new($args)
+    };
+}
+
+// If the feature of a strategy is disabled, the next one that fits is used. See the “Cargo Features” section of the crate documentation.
+cfg_if::cfg_if! {
+    if #[cfg(all(target_os = "windows", feature = "windows"))] {
+        create_strategies!(Windows, Windows);
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple", feature = "xdg"))] {
+        create_strategies!(Apple, Xdg);
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple"))] {
+        create_strategies!(Apple, Apple);
+    } else if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
+        create_strategies!(InMemory, InMemory);
+    } else if #[cfg(feature = "xdg")] {
+        create_strategies!(Xdg, Xdg);
+    } else {
+        create_strategies!(InMemory, InMemory);
     }
 }
 
+mod any;
+mod app;
+#[cfg(feature = "apple")]
 mod apple;
+mod cache;
+mod in_memory;
+mod lock;
+mod migration;
+#[cfg(feature = "unix")]
 mod unix;
+#[cfg(feature = "windows")]
 mod windows;
+#[cfg(feature = "xdg")]
 mod xdg;
 
+pub use any::{choose_app_strategy_enum, choose_native_strategy_enum, AnyAppStrategy};
+pub use app::App;
+#[cfg(feature = "apple")]
 pub use apple::Apple;
+pub use in_memory::InMemory;
+pub use lock::LockGuard;
+pub use migration::MigrationReport;
+#[cfg(feature = "unix")]
 pub use unix::Unix;
+#[cfg(feature = "windows")]
 pub use windows::Windows;
+#[cfg(feature = "xdg")]
 pub use xdg::Xdg;
@@ -0,0 +1,265 @@
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "apple")]
+use super::Apple;
+#[cfg(feature = "unix")]
+use super::Unix;
+#[cfg(feature = "windows")]
+use super::Windows;
+#[cfg(feature = "xdg")]
+use super::Xdg;
+use super::{AppStrategy, AppStrategyArgs, InMemory};
+use crate::{CreationError, DirSource, ResolvedDirs};
+
+/// Any of the app strategies provided by this crate, chosen at runtime without boxing.
+///
+/// This implements [`AppStrategy`](trait.AppStrategy.html) by forwarding every method to the strategy it holds, so it can be used wherever a `Box<dyn AppStrategy>` would be, without the allocation & dynamic dispatch (which `AppStrategy`’s generic methods rule out anyway).
+///
+/// ```
+/// # #[cfg(all(feature = "xdg", feature = "unix"))]
+/// # {
+/// use etcetera::app_strategy::{AnyAppStrategy, AppStrategy, AppStrategyArgs, Unix, Xdg};
+///
+/// let args = AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// };
+///
+/// let use_unix = std::env::var_os("FROBNICATOR_UNIX").is_some();
+/// let strategy: AnyAppStrategy = if use_unix {
+///     Unix::new(args.clone()).unwrap().into()
+/// } else {
+///     Xdg::new(args.clone()).unwrap().into()
+/// };
+///
+/// assert_eq!(strategy.config_dir(), Xdg::new(args).unwrap().config_dir());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum AnyAppStrategy {
+    /// The [`Apple`](struct.Apple.html) strategy.
+    #[cfg(feature = "apple")]
+    Apple(Apple),
+    /// The [`InMemory`](struct.InMemory.html) strategy.
+    InMemory(InMemory),
+    /// The [`Unix`](struct.Unix.html) strategy.
+    #[cfg(feature = "unix")]
+    Unix(Unix),
+    /// The [`Windows`](struct.Windows.html) strategy.
+    #[cfg(feature = "windows")]
+    Windows(Windows),
+    /// The [`Xdg`](struct.Xdg.html) strategy.
+    #[cfg(feature = "xdg")]
+    Xdg(Xdg),
+}
+
+macro_rules! delegate {
+    ($($name: ident -> $ret: ty),* $(,)?) => {
+        $(
+            fn $name(&self) -> $ret {
+                match self {
+                    #[cfg(feature = "apple")]
+                    Self::Apple(strategy) => strategy.$name(),
+                    Self::InMemory(strategy) => strategy.$name(),
+                    #[cfg(feature = "unix")]
+                    Self::Unix(strategy) => strategy.$name(),
+                    #[cfg(feature = "windows")]
+                    Self::Windows(strategy) => strategy.$name(),
+                    #[cfg(feature = "xdg")]
+                    Self::Xdg(strategy) => strategy.$name(),
+                }
+            }
+        )*
+    };
+    (in: $($name: ident -> $ret: ty),* $(,)?) => {
+        $(
+            fn $name<P: AsRef<OsStr>>(&self, path: P) -> $ret {
+                match self {
+                    #[cfg(feature = "apple")]
+                    Self::Apple(strategy) => strategy.$name(path),
+                    Self::InMemory(strategy) => strategy.$name(path),
+                    #[cfg(feature = "unix")]
+                    Self::Unix(strategy) => strategy.$name(path),
+                    #[cfg(feature = "windows")]
+                    Self::Windows(strategy) => strategy.$name(path),
+                    #[cfg(feature = "xdg")]
+                    Self::Xdg(strategy) => strategy.$name(path),
+                }
+            }
+        )*
+    };
+}
+
+impl AppStrategy for AnyAppStrategy {
+    fn home_dir(&self) -> &Path {
+        match self {
+            #[cfg(feature = "apple")]
+            Self::Apple(strategy) => strategy.home_dir(),
+            Self::InMemory(strategy) => strategy.home_dir(),
+            #[cfg(feature = "unix")]
+            Self::Unix(strategy) => strategy.home_dir(),
+            #[cfg(feature = "windows")]
+            Self::Windows(strategy) => strategy.home_dir(),
+            #[cfg(feature = "xdg")]
+            Self::Xdg(strategy) => strategy.home_dir(),
+        }
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        match self {
+            #[cfg(feature = "apple")]
+            Self::Apple(strategy) => strategy.env_var_os(key),
+            Self::InMemory(strategy) => strategy.env_var_os(key),
+            #[cfg(feature = "unix")]
+            Self::Unix(strategy) => strategy.env_var_os(key),
+            #[cfg(feature = "windows")]
+            Self::Windows(strategy) => strategy.env_var_os(key),
+            #[cfg(feature = "xdg")]
+            Self::Xdg(strategy) => strategy.env_var_os(key),
+        }
+    }
+
+    delegate!(
+        config_dir -> PathBuf,
+        data_dir -> PathBuf,
+        local_data_dir -> PathBuf,
+        cache_dir -> PathBuf,
+        state_dir -> Option<PathBuf>,
+        runtime_dir -> Option<PathBuf>,
+        runtime_dir_or_fallback -> io::Result<PathBuf>,
+        temp_dir -> PathBuf,
+        scratch_dir -> io::Result<PathBuf>,
+        log_dir -> Option<PathBuf>,
+        bin_dir -> PathBuf,
+        preference_dir -> PathBuf,
+        secret_dir -> Option<PathBuf>,
+        app_root -> Option<PathBuf>,
+        app_bin_dir -> Option<PathBuf>,
+        config_dirs -> Vec<PathBuf>,
+        data_dirs -> Vec<PathBuf>,
+        config_dir_with_source -> (PathBuf, DirSource),
+        data_dir_with_source -> (PathBuf, DirSource),
+        cache_dir_with_source -> (PathBuf, DirSource),
+        state_dir_with_source -> Option<(PathBuf, DirSource)>,
+        runtime_dir_with_source -> Option<(PathBuf, DirSource)>,
+        snapshot -> ResolvedDirs,
+        config_dir_cow -> Cow<'_, Path>,
+        data_dir_cow -> Cow<'_, Path>,
+        cache_dir_cow -> Cow<'_, Path>,
+        canonical_config_dir -> io::Result<PathBuf>,
+        canonical_data_dir -> io::Result<PathBuf>,
+        canonical_cache_dir -> io::Result<PathBuf>,
+        create_config_dir -> io::Result<PathBuf>,
+        create_data_dir -> io::Result<PathBuf>,
+        create_cache_dir -> io::Result<PathBuf>,
+        create_state_dir -> io::Result<Option<PathBuf>>,
+        create_runtime_dir -> io::Result<Option<PathBuf>>,
+        create_all_dirs -> io::Result<()>,
+    );
+
+    delegate!(in:
+        in_config_dir -> PathBuf,
+        in_data_dir -> PathBuf,
+        in_cache_dir -> PathBuf,
+        in_state_dir -> Option<PathBuf>,
+        in_runtime_dir -> Option<PathBuf>,
+        in_log_dir -> Option<PathBuf>,
+        in_bin_dir -> PathBuf,
+        find_config_file -> Option<PathBuf>,
+        find_all_config_files -> Vec<PathBuf>,
+    );
+}
+
+macro_rules! impl_from {
+    ($($strategy: ident),*) => {
+        $(
+            impl From<$strategy> for AnyAppStrategy {
+                fn from(strategy: $strategy) -> Self {
+                    Self::$strategy(strategy)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "apple")]
+impl_from!(Apple);
+impl_from!(InMemory);
+#[cfg(feature = "unix")]
+impl_from!(Unix);
+#[cfg(feature = "windows")]
+impl_from!(Windows);
+#[cfg(feature = "xdg")]
+impl_from!(Xdg);
+
+fn choose_overridden(
+    args: AppStrategyArgs,
+    default: impl FnOnce(AppStrategyArgs) -> Result<AnyAppStrategy, CreationError>,
+) -> Result<AnyAppStrategy, CreationError> {
+    match crate::base_strategy::strategy_override() {
+        Some(kind) => kind.app_strategy(args),
+        None => default(args),
+    }
+}
+
+macro_rules! create_strategies {
+    ($native: ident, $app: ident) => {
+        /// Returns the current OS’s native [`AppStrategy`](trait.AppStrategy.html), wrapped in an [`AnyAppStrategy`](enum.AnyAppStrategy.html).
+        /// See [`choose_native_strategy`](fn.choose_native_strategy.html) for which strategy this is. See [`choose_app_strategy_enum`](fn.choose_app_strategy_enum.html) for how to override it.
+        pub fn choose_native_strategy_enum(
+            args: AppStrategyArgs,
+        ) -> Result<AnyAppStrategy, CreationError> {
+            choose_overridden(args, |args| {
+                create_strategies!(@new $native, args).map(AnyAppStrategy::$native)
+            })
+        }
+
+        /// Returns the current OS’s default [`AppStrategy`](trait.AppStrategy.html), wrapped in an [`AnyAppStrategy`](enum.AnyAppStrategy.html).
+        /// See [`choose_app_strategy`](fn.choose_app_strategy.html) for which strategy this is.
+        ///
+        /// Like [`base_strategy::choose_base_strategy_enum`](../base_strategy/fn.choose_base_strategy_enum.html), this honours the `ETCETERA_STRATEGY` environment variable, which may also be set to `unix` here. Any value other than `xdg`, `windows`, `apple` or `unix` is ignored.
+        pub fn choose_app_strategy_enum(
+            args: AppStrategyArgs,
+        ) -> Result<AnyAppStrategy, CreationError> {
+            choose_overridden(args, |args| {
+                create_strategies!(@new $app, args).map(AnyAppStrategy::$app)
+            })
+        }
+    };
+    // See `base_strategy::choose_base_strategy`.
+    (@new InMemory, $args: ident) => {
+        if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+            InMemory::new($args)
+        } else {
+            crate::home_dir()
+                .map_err(CreationError::from)
+                .and_then(|home_dir| InMemory::with_root_dir($args, home_dir))
+        }
+    };
+    (@new $strategy: ident, $args: ident) => {
+        $strategy::new($args)
+    };
+}
+
+// If the feature of a strategy is disabled, the next one that fits is used. See the “Cargo Features” section of the crate documentation.
+cfg_if::cfg_if! {
+    if #[cfg(all(target_os = "windows", feature = "windows"))] {
+        create_strategies!(Windows, Windows);
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple", feature = "xdg"))] {
+        create_strategies!(Apple, Xdg);
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple"))] {
+        create_strategies!(Apple, Apple);
+    } else if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
+        create_strategies!(InMemory, InMemory);
+    } else if #[cfg(feature = "xdg")] {
+        create_strategies!(Xdg, Xdg);
+    } else {
+        create_strategies!(InMemory, InMemory);
+    }
+}
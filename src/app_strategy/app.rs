@@ -0,0 +1,158 @@
+use crate::base_strategy::{self, BaseStrategy};
+use crate::{CreationError, DirSource};
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// This strategy appends your application’s folder to the directories of any [`BaseStrategy`](../base_strategy/trait.BaseStrategy.html), so that you can combine e.g. the [`Custom`](../base_strategy/struct.Custom.html) or [`Portable`](../base_strategy/struct.Portable.html) base strategies with app-level naming.
+///
+/// The folder is named after [`unixy_name`](struct.AppStrategyArgs.html#method.unixy_name), just like for the [`Xdg`](struct.Xdg.html) strategy. As for `Xdg`, [`bin_dir`](trait.AppStrategy.html#tymethod.bin_dir) is the base strategy’s one, with no folder appended.
+///
+/// ```
+/// use etcetera::app_strategy::App;
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::base_strategy::Portable;
+/// use std::path::Path;
+///
+/// let root_path = if cfg!(windows) {
+///     "C:\\frobnicator\\"
+/// } else {
+///     "/frobnicator/"
+/// };
+///
+/// let app_strategy = App::new(
+///     Portable::with_root_dir(root_path.into()).unwrap(),
+///     AppStrategyArgs {
+///         top_level_domain: "org".to_string(),
+///         author: "Acme Corp".to_string(),
+///         app_name: "Frobnicator Plus".to_string(),
+///         ..Default::default()
+///     },
+/// ).unwrap();
+///
+/// assert_eq!(
+///     app_strategy.config_dir(),
+///     Path::new(root_path).join("config/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.data_dir(),
+///     Path::new(root_path).join("data/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.state_dir().unwrap(),
+///     Path::new(root_path).join("state/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir(),
+///     Path::new(root_path)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct App<B> {
+    base_strategy: B,
+    unixy_name: String,
+}
+
+impl<B: BaseStrategy> App<B> {
+    /// Create a new App AppStrategy on top of the given BaseStrategy
+    pub fn new(base_strategy: B, args: super::AppStrategyArgs) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy,
+            unixy_name: args.folder_name(args.unixy_name())?,
+        })
+    }
+
+    /// Gets a reference to the underlying BaseStrategy.
+    pub fn base_strategy(&self) -> &B {
+        &self.base_strategy
+    }
+}
+
+impl<B: BaseStrategy> super::AppStrategy for App<B> {
+    fn home_dir(&self) -> &Path {
+        self.base_strategy.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.base_strategy.env_var_os(key)
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.base_strategy.config_dir().join(&self.unixy_name)
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.base_strategy.data_dir().join(&self.unixy_name)
+    }
+
+    fn local_data_dir(&self) -> PathBuf {
+        self.base_strategy.local_data_dir().join(&self.unixy_name)
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.base_strategy.cache_dir().join(&self.unixy_name)
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .state_dir()
+            .map(|state_dir| state_dir.join(&self.unixy_name))
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .runtime_dir()
+            .map(|runtime_dir| runtime_dir.join(&self.unixy_name))
+    }
+
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        match self.runtime_dir() {
+            Some(runtime_dir) => Ok(runtime_dir),
+            None => base_strategy::fallback_runtime_dir(&self.unixy_name),
+        }
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        super::user_temp_dir(&self.unixy_name)
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .log_dir()
+            .map(|log_dir| log_dir.join(&self.unixy_name))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy.bin_dir()
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (config_dir, source) = self.base_strategy.config_dir_with_source();
+        (config_dir.join(&self.unixy_name), source)
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (data_dir, source) = self.base_strategy.data_dir_with_source();
+        (data_dir.join(&self.unixy_name), source)
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (cache_dir, source) = self.base_strategy.cache_dir_with_source();
+        (cache_dir.join(&self.unixy_name), source)
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.base_strategy
+            .state_dir_with_source()
+            .map(|(state_dir, source)| (state_dir.join(&self.unixy_name), source))
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.base_strategy
+            .runtime_dir_with_source()
+            .map(|(runtime_dir, source)| (runtime_dir.join(&self.unixy_name), source))
+    }
+}
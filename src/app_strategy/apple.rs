@@ -1,9 +1,14 @@
 use crate::base_strategy::BaseStrategy;
-use crate::{base_strategy, HomeDirError};
+use crate::{base_strategy, CreationError};
+use std::io;
 use std::path::{Path, PathBuf};
 
 /// This is the strategy created by Apple for use on macOS and iOS devices. It is always used by GUI apps on macOS, and is sometimes used by command-line applications there too. iOS only has GUIs, so all iOS applications follow this strategy. The specification is available [here](https://developer.apple.com/library/archive/documentation/FileManagement/Conceptual/FileSystemProgrammingGuide/FileSystemOverview/FileSystemOverview.html#//apple_ref/doc/uid/TP40010672-CH2-SW1).
 ///
+/// The folders are named after the application’s [bundle identifier](struct.AppStrategyArgs.html#method.bundle_id), which is composed from the [`AppStrategyArgs`](struct.AppStrategyArgs.html) unless you set its `bundle_id` field to use your registered identifier verbatim.
+///
+/// Apple’s platforms have no dedicated state folder, so the state directory is the `state` subfolder of the data directory, i.e. `~/Library/Application Support/<bundle ID>/state/`.
+///
 /// ```
 /// use etcetera::app_strategy::AppStrategy;
 /// use etcetera::app_strategy::AppStrategyArgs;
@@ -14,6 +19,7 @@ use std::path::{Path, PathBuf};
 ///     top_level_domain: "org".to_string(),
 ///     author: "Acme Corp".to_string(),
 ///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
 /// }).unwrap();
 ///
 /// let home_dir = etcetera::home_dir().unwrap();
@@ -35,13 +41,33 @@ use std::path::{Path, PathBuf};
 ///     Ok(Path::new("Library/Caches/org.acme-corp.Frobnicator-Plus/"))
 /// );
 /// assert_eq!(
-///     app_strategy.state_dir(),
-///     None
+///     app_strategy.state_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Library/Application Support/org.acme-corp.Frobnicator-Plus/state/"))
 /// );
 /// assert_eq!(
 ///     app_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Library/Logs/org.acme-corp.Frobnicator-Plus/"))
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("Library/Application Support/org.acme-corp.Frobnicator-Plus/bin/"))
+/// );
+/// assert_eq!(
+///     app_strategy.preference_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("Library/Preferences/org.acme-corp.Frobnicator-Plus/"))
+/// );
+/// assert_eq!(
+///     app_strategy.config_dirs(),
+///     vec![Path::new("/Library/Preferences/org.acme-corp.Frobnicator-Plus/")]
+/// );
+/// assert_eq!(
+///     app_strategy.data_dirs(),
+///     vec![Path::new("/Library/Application Support/org.acme-corp.Frobnicator-Plus/")]
+/// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Apple {
@@ -51,12 +77,53 @@ pub struct Apple {
 
 impl Apple {
     /// Create a new Apple AppStrategy
-    pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+    pub fn new(args: super::AppStrategyArgs) -> Result<Self, CreationError> {
+        args.validate()?;
+
         Ok(Self {
             base_strategy: base_strategy::Apple::new()?,
-            bundle_id: args.bundle_id(),
+            bundle_id: args.folder_name(args.bundle_id())?,
+        })
+    }
+
+    /// Create a new Apple AppStrategy using the given home directory instead of the current user’s one.
+    /// This returns an error if `home_dir` isn’t absolute.
+    pub fn with_home_dir(
+        args: super::AppStrategyArgs,
+        home_dir: PathBuf,
+    ) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy: base_strategy::Apple::with_home_dir(home_dir)?,
+            bundle_id: args.folder_name(args.bundle_id())?,
         })
     }
+
+    /// Gets the folder shared by the apps & extensions of the App Group `group_id`, i.e. `~/Library/Group Containers/<group_id>/`. See [`base_strategy::Apple::group_container_dir`](../base_strategy/struct.Apple.html#method.group_container_dir) for the format of `group_id`.
+    /// This returns an error if `group_id` cannot be used as a folder name.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::{Apple, AppStrategyArgs};
+    /// use etcetera::base_strategy;
+    ///
+    /// let app_strategy = Apple::new(AppStrategyArgs {
+    ///     top_level_domain: "com".to_string(),
+    ///     author: "Example".to_string(),
+    ///     app_name: "Frobnicator".to_string(),
+    ///     ..Default::default()
+    /// }).unwrap();
+    ///
+    /// let group_id = "group.com.example.frobnicator";
+    /// assert_eq!(
+    ///     app_strategy.group_container_dir(group_id).unwrap(),
+    ///     base_strategy::Apple::new().unwrap().group_container_dir(group_id).unwrap()
+    /// );
+    /// assert!(app_strategy.group_container_dir("../group.com.example.frobnicator").is_err());
+    /// ```
+    pub fn group_container_dir(&self, group_id: &str) -> Result<PathBuf, CreationError> {
+        self.base_strategy.group_container_dir(group_id)
+    }
 }
 
 impl super::AppStrategy for Apple {
@@ -77,10 +144,50 @@ impl super::AppStrategy for Apple {
     }
 
     fn state_dir(&self) -> Option<PathBuf> {
-        None
+        Some(self.data_dir().join("state/"))
     }
 
     fn runtime_dir(&self) -> Option<PathBuf> {
         None
     }
+
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        base_strategy::fallback_runtime_dir(&self.bundle_id)
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        super::user_temp_dir(&self.bundle_id)
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .log_dir()
+            .map(|log_dir| log_dir.join(&self.bundle_id))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.data_dir().join("bin/")
+    }
+
+    fn secret_dir(&self) -> Option<PathBuf> {
+        Some(self.data_dir().join("Secrets/"))
+    }
+
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        Some(self.bin_dir())
+    }
+
+    fn preference_dir(&self) -> PathBuf {
+        self.home_dir()
+            .join("Library/Preferences/")
+            .join(&self.bundle_id)
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        vec![Path::new("/Library/Preferences/").join(&self.bundle_id)]
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        vec![Path::new("/Library/Application Support/").join(&self.bundle_id)]
+    }
 }
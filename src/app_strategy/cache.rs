@@ -0,0 +1,81 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Symlinks are removed or skipped themselves rather than followed, so only the contents of the cache directory are ever touched.
+
+// See https://bford.info/cachedir/.
+const CACHEDIR_TAG: &str = "CACHEDIR.TAG";
+const CACHEDIR_TAG_SIGNATURE: &str = "Signature: 8a477f597d28d172789f06886806bc55";
+
+pub(super) fn clear(cache_dir: &Path) -> io::Result<()> {
+    for entry in read_dir_if_exists(cache_dir)?.into_iter().flatten() {
+        let entry = entry?;
+        // The directory is still a cache directory after it has been cleared.
+        if entry.file_name() == CACHEDIR_TAG {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            // This doesn’t follow symlinks either.
+            fs::remove_dir_all(entry.path())?;
+        } else if cfg!(windows) && file_type.is_symlink() && entry.path().is_dir() {
+            // Windows removes symlinks to directories like empty directories.
+            fs::remove_dir(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn size(cache_dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    let mut dirs = vec![cache_dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in read_dir_if_exists(&dir)?.into_iter().flatten() {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                dirs.push(entry.path());
+            } else if file_type.is_file() {
+                size += entry.metadata()?.len();
+            }
+        }
+    }
+
+    Ok(size)
+}
+
+pub(super) fn mark(cache_dir: &Path) -> io::Result<()> {
+    let tag = cache_dir.join(CACHEDIR_TAG);
+    match fs::read(&tag) {
+        Ok(contents) if contents.starts_with(CACHEDIR_TAG_SIGNATURE.as_bytes()) => return Ok(()),
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    fs::create_dir_all(cache_dir)?;
+    fs::write(
+        tag,
+        format!(
+            "{CACHEDIR_TAG_SIGNATURE}\n\
+             # This file is a cache directory tag created by etcetera.\n\
+             # For information about cache directory tags, see:\n\
+             #\thttps://bford.info/cachedir/\n"
+        ),
+    )
+}
+
+// A missing directory is the same as an empty one.
+fn read_dir_if_exists(dir: &Path) -> io::Result<Option<fs::ReadDir>> {
+    match fs::read_dir(dir) {
+        Ok(entries) => Ok(Some(entries)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
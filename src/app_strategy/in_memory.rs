@@ -0,0 +1,136 @@
+use crate::base_strategy::BaseStrategy;
+use crate::{base_strategy, CreationError};
+use std::path::{Path, PathBuf};
+
+/// This strategy never touches the OS: it appends your application’s name to the directories of the [`InMemory`](../base_strategy/struct.InMemory.html) base strategy, just like the [`Xdg`](struct.Xdg.html) strategy does. Everything is rooted at `/` by default.
+///
+/// It is meant for targets without a home directory, such as `wasm32-unknown-unknown`, where the `choose_*` functions return it, and for virtual filesystems.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::InMemory;
+/// use std::path::Path;
+///
+/// let app_strategy = InMemory::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// }).unwrap();
+///
+/// assert_eq!(
+///     app_strategy.home_dir(),
+///     Path::new("/")
+/// );
+/// assert_eq!(
+///     app_strategy.config_dir(),
+///     Path::new("/.config/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.data_dir(),
+///     Path::new("/.local/share/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.cache_dir(),
+///     Path::new("/.cache/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.state_dir().unwrap(),
+///     Path::new("/.local/state/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.runtime_dir(),
+///     None
+/// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap(),
+///     Path::new("/.local/state/logs/frobnicator-plus/")
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir(),
+///     Path::new("/.local/bin/")
+/// );
+/// assert_eq!(
+///     app_strategy.temp_dir(),
+///     Path::new("/tmp/frobnicator-plus/")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InMemory {
+    base_strategy: base_strategy::InMemory,
+    unixy_name: String,
+}
+
+impl InMemory {
+    /// Create a new InMemory AppStrategy rooted at `/`
+    pub fn new(args: super::AppStrategyArgs) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy: base_strategy::InMemory::new(),
+            unixy_name: args.folder_name(args.unixy_name())?,
+        })
+    }
+
+    /// Create a new InMemory AppStrategy rooted at the given directory.
+    /// This returns an error if `root_dir` isn’t absolute.
+    pub fn with_root_dir(
+        args: super::AppStrategyArgs,
+        root_dir: PathBuf,
+    ) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy: base_strategy::InMemory::with_root_dir(root_dir)?,
+            unixy_name: args.folder_name(args.unixy_name())?,
+        })
+    }
+}
+
+impl super::AppStrategy for InMemory {
+    fn home_dir(&self) -> &Path {
+        self.base_strategy.home_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.base_strategy.config_dir().join(&self.unixy_name)
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.base_strategy.data_dir().join(&self.unixy_name)
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.base_strategy.cache_dir().join(&self.unixy_name)
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .state_dir()
+            .map(|state_dir| state_dir.join(&self.unixy_name))
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .runtime_dir()
+            .map(|runtime_dir| runtime_dir.join(&self.unixy_name))
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .log_dir()
+            .map(|log_dir| log_dir.join(&self.unixy_name))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy.bin_dir()
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.base_strategy
+            .home_dir()
+            .join("tmp/")
+            .join(&self.unixy_name)
+    }
+}
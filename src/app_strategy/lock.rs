@@ -0,0 +1,132 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// An exclusive lock on a file, returned by [`AppStrategy::acquire_lock`](trait.AppStrategy.html#method.acquire_lock). The lock is released when this is dropped.
+#[derive(Debug)]
+pub struct LockGuard {
+    file: File,
+    path: PathBuf,
+}
+
+impl LockGuard {
+    /// Gets the path of the lock file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        // Closing the file releases the lock too, but Windows may take its time to do so.
+        let _ = unlock(&self.file);
+    }
+}
+
+pub(super) fn acquire(dir: &Path, name: &str) -> io::Result<LockGuard> {
+    // The name ends up in a path, so it mustn’t be able to escape `dir`.
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the name of a lock must be a plain file name",
+        ));
+    }
+
+    fs::create_dir_all(dir)?;
+
+    let path = dir.join(format!("{name}.lock"));
+    // The file is never removed, as another process could have opened it in the meantime, and would then hold a lock on a file that no longer exists.
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)?;
+    lock(&file)?;
+
+    Ok(LockGuard { file, path })
+}
+
+#[cfg(unix)]
+fn lock(file: &File) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: The file descriptor stays open for the duration of the call.
+    match unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } {
+        // `EWOULDBLOCK` maps to `io::ErrorKind::WouldBlock`.
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+#[cfg(unix)]
+fn unlock(file: &File) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: The file descriptor stays open for the duration of the call.
+    match unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_UN) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+#[cfg(windows)]
+fn lock(file: &File) -> io::Result<()> {
+    use std::os::windows::io::AsRawHandle;
+
+    use windows_sys::Win32::Foundation::ERROR_LOCK_VIOLATION;
+    use windows_sys::Win32::Storage::FileSystem::{
+        LockFileEx, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY,
+    };
+
+    // SAFETY: `OVERLAPPED` only contains integers & pointers, for which all zeroes are valid.
+    let mut overlapped = unsafe { std::mem::zeroed() };
+    // SAFETY: The handle stays open for the duration of the call, and `overlapped` is valid.
+    let locked = unsafe {
+        LockFileEx(
+            file.as_raw_handle(),
+            LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY,
+            0,
+            u32::MAX,
+            u32::MAX,
+            &mut overlapped,
+        )
+    };
+
+    match locked {
+        0 => match io::Error::last_os_error() {
+            // Unlike `EWOULDBLOCK` on Unix, this isn’t mapped to `io::ErrorKind::WouldBlock` by `std`.
+            error if error.raw_os_error() == Some(ERROR_LOCK_VIOLATION as i32) => {
+                Err(io::Error::new(io::ErrorKind::WouldBlock, error))
+            }
+            error => Err(error),
+        },
+        _ => Ok(()),
+    }
+}
+
+#[cfg(windows)]
+fn unlock(file: &File) -> io::Result<()> {
+    use std::os::windows::io::AsRawHandle;
+
+    use windows_sys::Win32::Storage::FileSystem::UnlockFile;
+
+    // SAFETY: The handle stays open for the duration of the call.
+    match unsafe { UnlockFile(file.as_raw_handle(), 0, 0, u32::MAX, u32::MAX) } {
+        0 => Err(io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn lock(_file: &File) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "file locking isn’t supported on this platform",
+    ))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn unlock(_file: &File) -> io::Result<()> {
+    Ok(())
+}
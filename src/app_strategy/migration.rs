@@ -0,0 +1,159 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::AppStrategy;
+
+/// What [`migrate_from`](trait.AppStrategy.html#method.migrate_from) did, as `(source, destination)` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MigrationReport {
+    /// The files & folders which were moved.
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    /// The files & folders which were left alone, because their destination already exists.
+    pub skipped: Vec<(PathBuf, PathBuf)>,
+}
+
+pub(super) fn migrate<N, O>(new: &N, old: &O) -> io::Result<MigrationReport>
+where
+    N: AppStrategy + ?Sized,
+    O: AppStrategy + ?Sized,
+{
+    // Some layouts nest directories inside each other, e.g. the `Unix` strategy keeps its data directory inside its configuration directory.
+    // These must be migrated on their own rather than as part of their parent.
+    let old_dirs: Vec<PathBuf> = [
+        Some(old.config_dir()),
+        Some(old.data_dir()),
+        Some(old.cache_dir()),
+        old.state_dir(),
+        old.runtime_dir(),
+        old.log_dir(),
+        Some(old.bin_dir()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut report = MigrationReport::default();
+
+    let dirs = [
+        (Some(old.config_dir()), Some(new.config_dir())),
+        (Some(old.data_dir()), Some(new.data_dir())),
+        (old.state_dir(), new.state_dir()),
+    ];
+    for (old_dir, new_dir) in dirs {
+        if let (Some(old_dir), Some(new_dir)) = (old_dir, new_dir) {
+            migrate_dir(&old_dir, &new_dir, &old_dirs, &mut report)?;
+        }
+    }
+
+    Ok(report)
+}
+
+fn migrate_dir(
+    old_dir: &Path,
+    new_dir: &Path,
+    old_dirs: &[PathBuf],
+    report: &mut MigrationReport,
+) -> io::Result<()> {
+    if old_dir == new_dir || !old_dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(old_dir)? {
+        let source = entry?.path();
+        if old_dirs.contains(&source) || new_dir.starts_with(&source) {
+            continue;
+        }
+
+        let destination = new_dir.join(source.file_name().unwrap_or_default());
+        if destination.symlink_metadata().is_ok() {
+            report.skipped.push((source, destination));
+        } else {
+            fs::create_dir_all(new_dir)?;
+            move_entry(&source, &destination)?;
+            report.moved.push((source, destination));
+        }
+    }
+
+    Ok(())
+}
+
+fn move_entry(source: &Path, destination: &Path) -> io::Result<()> {
+    // Renaming fails across filesystems, in which case we fall back to copying.
+    if fs::rename(source, destination).is_ok() {
+        return Ok(());
+    }
+
+    // The copy is made next to its destination & only renamed into place once complete, so that a failed copy isn’t mistaken for an earlier migration & skipped by the next one.
+    let mut partial_name = std::ffi::OsString::from(".");
+    partial_name.push(destination.file_name().unwrap_or_default());
+    partial_name.push(".etcetera-partial");
+    let partial = destination.with_file_name(partial_name);
+
+    // This is left over from an earlier migration which was interrupted.
+    if partial.symlink_metadata().is_ok() {
+        remove_entry(&partial)?;
+    }
+
+    if let Err(err) = copy_entry(source, &partial).and_then(|()| fs::rename(&partial, destination))
+    {
+        // The error we report is the one that made the copy fail, not this one.
+        let _ = remove_entry(&partial);
+        return Err(err);
+    }
+
+    remove_entry(source)
+}
+
+// Symbolic links are copied as links, rather than the files or folders they point to.
+fn copy_entry(source: &Path, destination: &Path) -> io::Result<()> {
+    let file_type = fs::symlink_metadata(source)?.file_type();
+    if file_type.is_symlink() {
+        copy_symlink(source, destination)
+    } else if file_type.is_dir() {
+        fs::create_dir(destination)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_entry(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, destination).map(|_| ())
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, destination: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(source)?, destination)
+}
+
+#[cfg(windows)]
+fn copy_symlink(source: &Path, destination: &Path) -> io::Result<()> {
+    use std::os::windows::fs::{symlink_dir, symlink_file};
+
+    // Windows has separate kinds of links for files & folders, so we keep the kind of the original.
+    let target = fs::read_link(source)?;
+    if source.is_dir() {
+        symlink_dir(target, destination)
+    } else {
+        symlink_file(target, destination)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn copy_symlink(source: &Path, destination: &Path) -> io::Result<()> {
+    fs::copy(source, destination).map(|_| ())
+}
+
+// Like `copy_entry`, this doesn’t follow symbolic links.
+fn remove_entry(path: &Path) -> io::Result<()> {
+    let file_type = fs::symlink_metadata(path)?.file_type();
+    if file_type.is_dir() {
+        fs::remove_dir_all(path)
+    } else if cfg!(windows) && file_type.is_symlink() && path.is_dir() {
+        // A link to a folder is removed like an empty folder on Windows.
+        fs::remove_dir(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
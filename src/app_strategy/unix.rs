@@ -1,9 +1,21 @@
 use std::path::{Path, PathBuf};
 
-use crate::HomeDirError;
+use crate::CreationError;
 
 /// This strategy has no standard or official specification. It has arisen over time through hundreds of Unixy tools. Vim and Cargo are notable examples whose configuration/data/cache directory layouts are similar to those created by this strategy.
 ///
+/// Everything lives inside a single hidden folder in the home directory, named after [`unixy_name`](struct.AppStrategyArgs.html#method.unixy_name): configuration files are placed directly inside it, while the other directories are subfolders of it.
+///
+/// | Directory | Location                 |
+/// |-----------|--------------------------|
+/// | config    | `~/.myapp/`              |
+/// | data      | `~/.myapp/data/`         |
+/// | cache     | `~/.myapp/cache/`        |
+/// | state     | `~/.myapp/state/`        |
+/// | runtime   | `~/.myapp/runtime/`      |
+/// | log       | `~/.myapp/logs/`         |
+/// | bin       | `~/.myapp/bin/`          |
+///
 /// ```
 /// use etcetera::app_strategy::AppStrategy;
 /// use etcetera::app_strategy::AppStrategyArgs;
@@ -14,6 +26,7 @@ use crate::HomeDirError;
 ///     top_level_domain: "org".to_string(),
 ///     author: "Acme Corp".to_string(),
 ///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
 /// }).unwrap();
 ///
 /// let home_dir = etcetera::home_dir().unwrap();
@@ -42,6 +55,14 @@ use crate::HomeDirError;
 ///     app_strategy.runtime_dir().unwrap().strip_prefix(&home_dir),
 ///     Ok(Path::new(".frobnicator-plus/runtime/"))
 /// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new(".frobnicator-plus/logs/"))
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".frobnicator-plus/bin/"))
+/// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Unix {
@@ -52,10 +73,51 @@ pub struct Unix {
 
 impl Unix {
     /// Create a new Unix AppStrategy
-    pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+    pub fn new(args: super::AppStrategyArgs) -> Result<Self, CreationError> {
+        args.validate()?;
+
         Ok(Self {
             home_dir: crate::home_dir()?,
-            unixy_name: format!(".{}", args.unixy_name()),
+            unixy_name: args.folder_name(format!(".{}", args.unixy_name()))?,
+        })
+    }
+
+    /// Create a new Unix AppStrategy using the given home directory instead of the current user’s one.
+    /// This returns an error if `home_dir` isn’t absolute.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Unix;
+    /// use std::path::Path;
+    ///
+    /// let home_path = if cfg!(windows) {
+    ///     "C:\\my_home_location\\"
+    /// } else {
+    ///     "/my_home_location/"
+    /// };
+    ///
+    /// let app_strategy = Unix::with_home_dir(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, home_path.into()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     app_strategy.config_dir(),
+    ///     Path::new(home_path).join(".frobnicator-plus/")
+    /// );
+    /// ```
+    pub fn with_home_dir(
+        args: super::AppStrategyArgs,
+        home_dir: PathBuf,
+    ) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            home_dir: crate::validate_home_dir(home_dir)?,
+            unixy_name: args.folder_name(format!(".{}", args.unixy_name()))?,
         })
     }
 }
@@ -84,4 +146,29 @@ impl super::AppStrategy for Unix {
     fn runtime_dir(&self) -> Option<PathBuf> {
         Some(self.home_dir.join(&self.unixy_name).join("runtime/"))
     }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join(&self.unixy_name).join("logs/"))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir.join(&self.unixy_name).join("bin/")
+    }
+
+    fn app_root(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join(&self.unixy_name))
+    }
+
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        Some(self.bin_dir())
+    }
+
+    // The folder doesn’t need to be hidden outside of the home directory.
+    fn temp_dir(&self) -> PathBuf {
+        let unixy_name = self
+            .unixy_name
+            .strip_prefix('.')
+            .unwrap_or(&self.unixy_name);
+        super::user_temp_dir(unixy_name)
+    }
 }
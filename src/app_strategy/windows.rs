@@ -1,5 +1,7 @@
 use crate::base_strategy::BaseStrategy;
-use crate::{base_strategy, HomeDirError};
+use crate::env::{EnvProvider, StdEnv};
+use crate::{base_strategy, CreationError, DirSource};
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 
 /// This strategy follows Windows’ conventions. It seems that all Windows GUI apps, and some command-line ones follow this pattern. The specification is available [here](https://docs.microsoft.com/en-us/windows/win32/shell/knownfolderid).
@@ -23,6 +25,7 @@ use std::path::{Path, PathBuf};
 ///     top_level_domain: "org".to_string(),
 ///     author: "Acme Corp".to_string(),
 ///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
 /// }).unwrap();
 ///
 /// let home_dir = etcetera::home_dir().unwrap();
@@ -44,16 +47,28 @@ use std::path::{Path, PathBuf};
 ///     Ok(Path::new("AppData/Local/Acme Corp/Frobnicator Plus/cache"))
 /// );
 /// assert_eq!(
-///     app_strategy.state_dir(),
-///     None
+///     app_strategy.local_data_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Acme Corp/Frobnicator Plus/data"))
 /// );
 /// assert_eq!(
-///     app_strategy.runtime_dir(),
-///     None
+///     app_strategy.state_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Acme Corp/Frobnicator Plus/state"))
+/// );
+/// assert_eq!(
+///     app_strategy.runtime_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Temp/Acme Corp/Frobnicator Plus"))
+/// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Logs/Acme Corp/Frobnicator Plus"))
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Acme Corp/Frobnicator Plus/bin"))
 /// );
 /// ```
 ///
-/// This next example gives the environment variables values:
+/// The environment variables are only used if `SHGetKnownFolderPath` fails, so this next example, which gives them values, only applies outside of Windows:
 ///
 /// ```
 /// use etcetera::app_strategy::AppStrategy;
@@ -81,10 +96,15 @@ use std::path::{Path, PathBuf};
 /// std::env::set_var("APPDATA", data_path);
 /// std::env::set_var("LOCALAPPDATA", cache_path);
 ///
+/// if cfg!(windows) {
+///     return;
+/// }
+///
 /// let app_strategy = Windows::new(AppStrategyArgs {
 ///     top_level_domain: "org".to_string(),
 ///     author: "Acme Corp".to_string(),
 ///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
 /// }).unwrap();
 ///
 /// assert_eq!(
@@ -104,18 +124,26 @@ use std::path::{Path, PathBuf};
 ///     Path::new(&format!("{}/Acme Corp/Frobnicator Plus/cache", cache_path))
 /// );
 /// assert_eq!(
-///     app_strategy.state_dir(),
-///     None
+///     app_strategy.state_dir().unwrap(),
+///     Path::new(&format!("{}/Acme Corp/Frobnicator Plus/state", cache_path))
 /// );
 /// assert_eq!(
-///     app_strategy.runtime_dir(),
-///     None
+///     app_strategy.runtime_dir().unwrap(),
+///     Path::new(cache_path).join("Temp").join("Acme Corp/Frobnicator Plus")
+/// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap(),
+///     Path::new(cache_path).join("Logs").join("Acme Corp/Frobnicator Plus")
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir(),
+///     Path::new(&format!("{}/Acme Corp/Frobnicator Plus/bin", cache_path))
 /// );
 /// ```
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Windows {
-    base_strategy: base_strategy::Windows,
+pub struct Windows<E = StdEnv> {
+    base_strategy: base_strategy::Windows<E>,
     author_app_name_path: PathBuf,
 }
 
@@ -131,19 +159,76 @@ macro_rules! dir_method {
 
 impl Windows {
     /// Create a new Windows AppStrategy
-    pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+    pub fn new(args: super::AppStrategyArgs) -> Result<Self, CreationError> {
+        args.validate()?;
+
         Ok(Self {
             base_strategy: base_strategy::Windows::new()?,
-            author_app_name_path: PathBuf::from(args.author).join(args.app_name),
+            author_app_name_path: PathBuf::from(args.folder_name(args.author.clone())?)
+                .join(args.folder_name(args.app_name.clone())?),
+        })
+    }
+
+    /// Create a new Windows AppStrategy using the given home directory instead of the current user’s one.
+    /// This returns an error if `home_dir` isn’t absolute.
+    pub fn with_home_dir(
+        args: super::AppStrategyArgs,
+        home_dir: PathBuf,
+    ) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy: base_strategy::Windows::with_home_dir(home_dir)?,
+            author_app_name_path: PathBuf::from(args.folder_name(args.author.clone())?)
+                .join(args.folder_name(args.app_name.clone())?),
+        })
+    }
+}
+
+impl<E: EnvProvider> Windows<E> {
+    /// Create a new Windows AppStrategy which reads `APPDATA` & `LOCALAPPDATA` from `env` instead of the process’ environment.
+    /// These are only used if `SHGetKnownFolderPath` fails.
+    pub fn new_with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy: base_strategy::Windows::new_with_env(env)?,
+            author_app_name_path: PathBuf::from(args.folder_name(args.author.clone())?)
+                .join(args.folder_name(args.app_name.clone())?),
         })
     }
+
+    /// Replaces the environment `APPDATA` & `LOCALAPPDATA` are read from.
+    pub fn with_env<F: EnvProvider>(self, env: F) -> Windows<F> {
+        Windows {
+            base_strategy: self.base_strategy.with_env(env),
+            author_app_name_path: self.author_app_name_path,
+        }
+    }
+}
+
+impl<E> Windows<E> {
+    fn dir_with_source(
+        &self,
+        (base_dir, source): (PathBuf, DirSource),
+        subfolder_name: &str,
+    ) -> (PathBuf, DirSource) {
+        let path = base_dir
+            .join(&self.author_app_name_path)
+            .join(subfolder_name);
+        (path, source)
+    }
 }
 
-impl super::AppStrategy for Windows {
+impl<E: EnvProvider> super::AppStrategy for Windows<E> {
     fn home_dir(&self) -> &Path {
         self.base_strategy.home_dir()
     }
 
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.base_strategy.env_var_os(key)
+    }
+
     fn config_dir(&self) -> PathBuf {
         dir_method!(self, config_dir, "config")
     }
@@ -152,15 +237,89 @@ impl super::AppStrategy for Windows {
         dir_method!(self, data_dir, "data")
     }
 
+    fn local_data_dir(&self) -> PathBuf {
+        dir_method!(self, cache_dir, "data")
+    }
+
     fn cache_dir(&self) -> PathBuf {
         dir_method!(self, cache_dir, "cache")
     }
 
     fn state_dir(&self) -> Option<PathBuf> {
-        None
+        Some(dir_method!(self, cache_dir, "state"))
+    }
+
+    fn secret_dir(&self) -> Option<PathBuf> {
+        Some(dir_method!(self, cache_dir, "secrets"))
+    }
+
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        Some(self.bin_dir())
     }
 
     fn runtime_dir(&self) -> Option<PathBuf> {
-        None
+        self.base_strategy
+            .runtime_dir()
+            .map(|runtime_dir| runtime_dir.join(&self.author_app_name_path))
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        super::user_temp_dir(&self.author_app_name_path)
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .log_dir()
+            .map(|log_dir| log_dir.join(&self.author_app_name_path))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        dir_method!(self, cache_dir, "bin")
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.dir_with_source(self.base_strategy.config_dir_with_source(), "config")
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.dir_with_source(self.base_strategy.data_dir_with_source(), "data")
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.dir_with_source(self.base_strategy.cache_dir_with_source(), "cache")
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        Some(self.dir_with_source(self.base_strategy.cache_dir_with_source(), "state"))
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.base_strategy
+            .runtime_dir_with_source()
+            .map(|(runtime_dir, source)| (runtime_dir.join(&self.author_app_name_path), source))
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.base_strategy
+            .program_data_dir()
+            .map(|program_data_dir| {
+                program_data_dir
+                    .join(&self.author_app_name_path)
+                    .join("config")
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.base_strategy
+            .program_data_dir()
+            .map(|program_data_dir| {
+                program_data_dir
+                    .join(&self.author_app_name_path)
+                    .join("data")
+            })
+            .into_iter()
+            .collect()
     }
 }
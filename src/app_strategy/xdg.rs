@@ -1,5 +1,8 @@
 use crate::base_strategy::BaseStrategy;
-use crate::{base_strategy, HomeDirError};
+use crate::env::{EnvProvider, StdEnv};
+use crate::{base_strategy, CreationError, DirSource};
+use std::ffi::OsString;
+use std::io;
 use std::path::{Path, PathBuf};
 
 /// This strategy implements the [XDG Base Directories Specification](https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html). It is the most common on Linux, but is increasingly being adopted elsewhere.
@@ -18,11 +21,13 @@ use std::path::{Path, PathBuf};
 /// std::env::remove_var("XDG_CACHE_HOME");
 /// std::env::remove_var("XDG_STATE_HOME");
 /// std::env::remove_var("XDG_RUNTIME_DIR");
+/// std::env::remove_var("XDG_BIN_HOME");
 ///
 /// let app_strategy = Xdg::new(AppStrategyArgs {
 ///     top_level_domain: "org".to_string(),
 ///     author: "Acme Corp".to_string(),
 ///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
 /// }).unwrap();
 ///
 /// let home_dir = etcetera::home_dir().unwrap();
@@ -51,6 +56,18 @@ use std::path::{Path, PathBuf};
 ///     app_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/state/logs/frobnicator-plus/"))
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/bin/"))
+/// );
+/// assert_eq!(
+///     app_strategy.preference_dir(),
+///     app_strategy.config_dir()
+/// );
 /// ```
 ///
 /// This next example gives the environment variables values:
@@ -98,6 +115,7 @@ use std::path::{Path, PathBuf};
 ///     top_level_domain: "org".to_string(),
 ///     author: "Acme Corp".to_string(),
 ///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
 /// }).unwrap();
 ///
 /// assert_eq!(
@@ -120,6 +138,10 @@ use std::path::{Path, PathBuf};
 ///     app_strategy.runtime_dir().unwrap(),
 ///     Path::new(&format!("{}/frobnicator-plus/", runtime_path))
 /// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap(),
+///     Path::new(&format!("{}/logs/frobnicator-plus/", state_path))
+/// );
 /// ```
 ///
 /// The XDG spec requires that when the environment variables’ values are not absolute paths, their values should be ignored. This example exemplifies this behaviour:
@@ -136,11 +158,13 @@ use std::path::{Path, PathBuf};
 /// std::env::set_var("XDG_CACHE_HOME", "yet_another/");
 /// std::env::set_var("XDG_STATE_HOME", "./and_another");
 /// std::env::set_var("XDG_RUNTIME_DIR", "relative_path/");
+/// std::env::set_var("XDG_BIN_HOME", "relative_path/");
 ///
 /// let app_strategy = Xdg::new(AppStrategyArgs {
 ///     top_level_domain: "org".to_string(),
 ///     author: "Acme Corp".to_string(),
 ///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
 /// }).unwrap();
 ///
 /// let home_dir = etcetera::home_dir().unwrap();
@@ -166,28 +190,128 @@ use std::path::{Path, PathBuf};
 ///     app_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     app_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/state/logs/frobnicator-plus/"))
+/// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/bin/"))
+/// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Xdg {
-    base_strategy: base_strategy::Xdg,
+pub struct Xdg<E = StdEnv> {
+    base_strategy: base_strategy::Xdg<E>,
     unixy_name: String,
 }
 
 impl Xdg {
     /// Create a new Xdg AppStrategy
-    pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+    pub fn new(args: super::AppStrategyArgs) -> Result<Self, CreationError> {
+        args.validate()?;
+
         Ok(Self {
             base_strategy: base_strategy::Xdg::new()?,
-            unixy_name: args.unixy_name(),
+            unixy_name: args.folder_name(args.unixy_name())?,
         })
     }
+
+    /// Create a new Xdg AppStrategy using the given home directory instead of the current user’s one.
+    /// This returns an error if `home_dir` isn’t absolute.
+    ///
+    /// Environment variables such as `XDG_CONFIG_HOME` still take precedence over the directories derived from `home_dir`.
+    pub fn with_home_dir(
+        args: super::AppStrategyArgs,
+        home_dir: PathBuf,
+    ) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy: base_strategy::Xdg::with_home_dir(home_dir)?,
+            unixy_name: args.folder_name(args.unixy_name())?,
+        })
+    }
+}
+
+impl<E: EnvProvider> Xdg<E> {
+    /// Create a new Xdg AppStrategy which reads the XDG environment variables from `env` instead of the process’ environment.
+    /// See the [`env`](../env/index.html) module for more information.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use std::collections::HashMap;
+    /// use std::path::Path;
+    ///
+    /// let data_path = if cfg!(windows) {
+    ///     "C:\\my_data_location\\"
+    /// } else {
+    ///     "/my_data_location/"
+    /// };
+    ///
+    /// let app_strategy = Xdg::new_with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }, HashMap::from([("XDG_DATA_HOME", data_path)])).unwrap();
+    ///
+    /// assert_eq!(
+    ///     app_strategy.data_dir(),
+    ///     Path::new(data_path).join("frobnicator-plus")
+    /// );
+    /// ```
+    pub fn new_with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, CreationError> {
+        args.validate()?;
+
+        Ok(Self {
+            base_strategy: base_strategy::Xdg::new_with_env(env)?,
+            unixy_name: args.folder_name(args.unixy_name())?,
+        })
+    }
+
+    /// Replaces the environment the XDG environment variables are read from.
+    pub fn with_env<F: EnvProvider>(self, env: F) -> Xdg<F> {
+        Xdg {
+            base_strategy: self.base_strategy.with_env(env),
+            unixy_name: self.unixy_name,
+        }
+    }
+
+    /// Sets whether values of the XDG environment variables which start with `~` are expanded against the home directory.
+    /// See [`base_strategy::Xdg::with_tilde_expansion`](../base_strategy/struct.Xdg.html#method.with_tilde_expansion) for more information.
+    pub fn with_tilde_expansion(mut self, expand_tilde: bool) -> Self {
+        self.base_strategy = self.base_strategy.with_tilde_expansion(expand_tilde);
+        self
+    }
+
+    /// Sets whether references to other environment variables in the values of the XDG environment variables, e.g. `$HOME/.data`, are expanded.
+    /// See [`base_strategy::Xdg::with_var_expansion`](../base_strategy/struct.Xdg.html#method.with_var_expansion) for more information.
+    pub fn with_var_expansion(mut self, expand_vars: bool) -> Self {
+        self.base_strategy = self.base_strategy.with_var_expansion(expand_vars);
+        self
+    }
+
+    /// Sets whether `XDG_RUNTIME_DIR` is checked against the requirements of the XDG spec before it is used.
+    /// See [`base_strategy::Xdg::with_runtime_dir_validation`](../base_strategy/struct.Xdg.html#method.with_runtime_dir_validation) for more information.
+    pub fn with_runtime_dir_validation(mut self, validate_runtime_dir: bool) -> Self {
+        self.base_strategy = self
+            .base_strategy
+            .with_runtime_dir_validation(validate_runtime_dir);
+        self
+    }
 }
 
-impl super::AppStrategy for Xdg {
+impl<E: EnvProvider> super::AppStrategy for Xdg<E> {
     fn home_dir(&self) -> &Path {
         self.base_strategy.home_dir()
     }
 
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.base_strategy.env_var_os(key)
+    }
+
     fn config_dir(&self) -> PathBuf {
         self.base_strategy.config_dir().join(&self.unixy_name)
     }
@@ -201,12 +325,9 @@ impl super::AppStrategy for Xdg {
     }
 
     fn state_dir(&self) -> Option<PathBuf> {
-        Some(
-            self.base_strategy
-                .state_dir()
-                .unwrap()
-                .join(&self.unixy_name),
-        )
+        self.base_strategy
+            .state_dir()
+            .map(|state_dir| state_dir.join(&self.unixy_name))
     }
 
     fn runtime_dir(&self) -> Option<PathBuf> {
@@ -214,4 +335,68 @@ impl super::AppStrategy for Xdg {
             .runtime_dir()
             .map(|runtime_dir| runtime_dir.join(&self.unixy_name))
     }
+
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        match self.runtime_dir() {
+            Some(runtime_dir) => Ok(runtime_dir),
+            None => base_strategy::fallback_runtime_dir(&self.unixy_name),
+        }
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        super::user_temp_dir(&self.unixy_name)
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .log_dir()
+            .map(|log_dir| log_dir.join(&self.unixy_name))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy.bin_dir()
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (config_dir, source) = self.base_strategy.config_dir_with_source();
+        (config_dir.join(&self.unixy_name), source)
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (data_dir, source) = self.base_strategy.data_dir_with_source();
+        (data_dir.join(&self.unixy_name), source)
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (cache_dir, source) = self.base_strategy.cache_dir_with_source();
+        (cache_dir.join(&self.unixy_name), source)
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.base_strategy
+            .state_dir_with_source()
+            .map(|(state_dir, source)| (state_dir.join(&self.unixy_name), source))
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.base_strategy
+            .runtime_dir_with_source()
+            .map(|(runtime_dir, source)| (runtime_dir.join(&self.unixy_name), source))
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.base_strategy
+            .config_dirs()
+            .into_iter()
+            .map(|config_dir| config_dir.join(&self.unixy_name))
+            .collect()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.base_strategy
+            .data_dirs()
+            .into_iter()
+            .map(|data_dir| data_dir.join(&self.unixy_name))
+            .collect()
+    }
 }
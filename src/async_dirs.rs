@@ -0,0 +1,142 @@
+use std::io;
+use std::path::PathBuf;
+
+use crate::app_strategy::AppStrategy;
+use crate::fs::{DEFAULT_DIR_MODE, PRIVATE_DIR_MODE};
+
+/// Borrows an [`AppStrategy`](app_strategy/trait.AppStrategy.html) and creates its directories through [`tokio::fs`](https://docs.rs/tokio/latest/tokio/fs/index.html), so that async applications can set up their directories without blocking the executor.
+///
+/// Only creating the directories is asynchronous: computing their paths is cheap, so it stays synchronous and is done by the strategy as usual. As with `tokio::fs`, these methods must be called from within a Tokio runtime.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+/// use etcetera::AsyncDirs;
+///
+/// let root = std::env::temp_dir().join(format!("etcetera-async-dirs-{}", std::process::id()));
+/// std::env::set_var("XDG_CONFIG_HOME", root.join("config"));
+/// std::env::set_var("XDG_DATA_HOME", root.join("data"));
+/// std::env::set_var("XDG_CACHE_HOME", root.join("cache"));
+/// std::env::set_var("XDG_STATE_HOME", root.join("state"));
+///
+/// let app_strategy = Xdg::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// }).unwrap();
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// runtime.block_on(async {
+///     let dirs = AsyncDirs::new(&app_strategy);
+///     assert_eq!(dirs.create_config_dir().await.unwrap(), app_strategy.config_dir());
+///     dirs.create_all_dirs().await.unwrap();
+/// });
+///
+/// assert!(app_strategy.config_dir().is_dir());
+/// assert!(app_strategy.data_dir().is_dir());
+/// assert!(app_strategy.cache_dir().is_dir());
+/// assert!(app_strategy.state_dir().unwrap().is_dir());
+///
+/// // Like `AppStrategy::create_all_dirs`, the state directory is private.
+/// #[cfg(unix)]
+/// {
+///     use std::os::unix::fs::PermissionsExt;
+///     let metadata = std::fs::metadata(app_strategy.state_dir().unwrap()).unwrap();
+///     assert_eq!(metadata.permissions().mode() & 0o777, 0o700);
+/// }
+///
+/// std::fs::remove_dir_all(&root).unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncDirs<'a, S: ?Sized> {
+    strategy: &'a S,
+}
+
+impl<'a, S: AppStrategy + ?Sized> AsyncDirs<'a, S> {
+    /// Wraps the given strategy.
+    pub fn new(strategy: &'a S) -> Self {
+        Self { strategy }
+    }
+
+    /// Gets the wrapped strategy.
+    pub fn strategy(&self) -> &'a S {
+        self.strategy
+    }
+
+    /// Creates your application’s configuration directory (and any missing parents) and returns its path.
+    pub async fn create_config_dir(&self) -> io::Result<PathBuf> {
+        create_dir(self.strategy.config_dir(), DEFAULT_DIR_MODE).await
+    }
+
+    /// Creates your application’s data directory (and any missing parents) and returns its path.
+    pub async fn create_data_dir(&self) -> io::Result<PathBuf> {
+        create_dir(self.strategy.data_dir(), DEFAULT_DIR_MODE).await
+    }
+
+    /// Creates your application’s cache directory (and any missing parents) and returns its path.
+    pub async fn create_cache_dir(&self) -> io::Result<PathBuf> {
+        create_dir(self.strategy.cache_dir(), DEFAULT_DIR_MODE).await
+    }
+
+    /// Creates your application’s state directory (and any missing parents) and returns its path.
+    /// On Unix, it is only accessible to its owner, see [`fs::PRIVATE_DIR_MODE`](fs/constant.PRIVATE_DIR_MODE.html).
+    ///
+    /// Returns `Ok(None)` without touching the filesystem if the strategy has no state directory.
+    pub async fn create_state_dir(&self) -> io::Result<Option<PathBuf>> {
+        create_opt_dir(self.strategy.state_dir(), PRIVATE_DIR_MODE).await
+    }
+
+    /// Creates your application’s runtime directory (and any missing parents) and returns its path.
+    /// On Unix, it is only accessible to its owner, see [`fs::PRIVATE_DIR_MODE`](fs/constant.PRIVATE_DIR_MODE.html).
+    ///
+    /// Returns `Ok(None)` without touching the filesystem if the strategy has no runtime directory.
+    pub async fn create_runtime_dir(&self) -> io::Result<Option<PathBuf>> {
+        create_opt_dir(self.strategy.runtime_dir(), PRIVATE_DIR_MODE).await
+    }
+
+    /// Creates your application’s configuration, data, cache & state directories in one go, like [`AppStrategy::create_all_dirs`](app_strategy/trait.AppStrategy.html#method.create_all_dirs).
+    pub async fn create_all_dirs(&self) -> io::Result<()> {
+        self.create_config_dir().await?;
+        self.create_data_dir().await?;
+        self.create_cache_dir().await?;
+        self.create_state_dir().await?;
+        Ok(())
+    }
+}
+
+// Like `StdFs::create_dir_all_with_mode`, only `path` itself gets `mode`, and existing directories are left alone.
+async fn create_dir(path: PathBuf, mode: u32) -> io::Result<PathBuf> {
+    #[cfg(unix)]
+    {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        if let Err(err) = tokio::fs::DirBuilder::new().mode(mode).create(&path).await {
+            let is_dir = tokio::fs::metadata(&path)
+                .await
+                .is_ok_and(|metadata| metadata.is_dir());
+            if err.kind() != io::ErrorKind::AlreadyExists || !is_dir {
+                return Err(err);
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = mode;
+        tokio::fs::create_dir_all(&path).await?;
+    }
+
+    Ok(path)
+}
+
+async fn create_opt_dir(path: Option<PathBuf>, mode: u32) -> io::Result<Option<PathBuf>> {
+    match path {
+        Some(path) => create_dir(path, mode).await.map(Some),
+        None => Ok(None),
+    }
+}
@@ -1,6 +1,9 @@
 //! These strategies simply provide the user’s configuration, data, and cache directories, without knowing about the application specifically.
 
-use crate::HomeDirError;
+use crate::{DirClassification, DirKind, DirSource, HomeDirError, ResolvedDirs};
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::io;
 use std::path::{Path, PathBuf};
 
 /// Provides configuration, data, and cache directories of the current user.
@@ -8,62 +11,557 @@ pub trait BaseStrategy {
     /// Gets the home directory of the current user.
     fn home_dir(&self) -> &Path;
 
+    /// Gets an owned copy of [`home_dir`](#tymethod.home_dir), e.g. to keep it after a temporary strategy is gone.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::{choose_base_strategy, BaseStrategy};
+    ///
+    /// let home_dir = choose_base_strategy().unwrap().home_dir_owned();
+    /// assert_eq!(home_dir, etcetera::home_dir().unwrap());
+    /// ```
+    fn home_dir_owned(&self) -> PathBuf {
+        self.home_dir().to_path_buf()
+    }
+
+    /// Gets the value of the environment variable `key` as this strategy sees it, i.e. from its [`EnvProvider`](../env/trait.EnvProvider.html) if it has one, or from the current process otherwise.
+    ///
+    /// Methods reading variables other than the strategy’s own, such as [`user_executable_dir`](#method.user_executable_dir) reading `PATH`, go through this, so that they respect a custom environment too.
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+
     /// Gets the user’s configuration directory.
     fn config_dir(&self) -> PathBuf;
 
     /// Gets the user’s data directory.
     fn data_dir(&self) -> PathBuf;
 
+    /// Gets the user’s machine-specific data directory, for data which shouldn’t follow the user to other machines.
+    ///
+    /// On Windows, [`data_dir`](#tymethod.data_dir) is part of the roaming profile (`AppData\Roaming`), so this is `AppData\Local` instead. Every other strategy has no such distinction, so this is the same as `data_dir`.
+    fn local_data_dir(&self) -> PathBuf {
+        self.data_dir()
+    }
+
     /// Gets the user’s cache directory.
     fn cache_dir(&self) -> PathBuf;
 
     /// Gets the user’s state directory.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) strategy supports this.
+    /// Currently, only the [`Xdg`](struct.Xdg.html), [`Windows`](struct.Windows.html) & [`Apple`](struct.Apple.html) strategies support this.
     fn state_dir(&self) -> Option<PathBuf>;
 
     /// Gets the user’s runtime directory.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) strategy supports this.
+    /// Currently, only the [`Xdg`](struct.Xdg.html) & [`Windows`](struct.Windows.html) strategies support this.
     ///
     /// Note: The [XDG Base Directory Specification](spec) places additional requirements on this
-    /// directory related to ownership, permissions, and persistence. This library does not check
-    /// these requirements.
+    /// directory related to ownership, permissions, and persistence. By default, this library does
+    /// not check these requirements; enable [`Xdg::with_runtime_dir_validation`](struct.Xdg.html#method.with_runtime_dir_validation)
+    /// to check the ownership & permissions on Unix, in which case an invalid `XDG_RUNTIME_DIR` is ignored.
     ///
     /// [spec]: https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html
     fn runtime_dir(&self) -> Option<PathBuf>;
+
+    /// Gets the user’s runtime directory, or a fallback inside the system’s temporary directory if the strategy has none, e.g. because `XDG_RUNTIME_DIR` isn’t set in an SSH session.
+    ///
+    /// The fallback is `runtime-<uid>` inside [`std::env::temp_dir`] (which respects `TMPDIR`) on Unix, and `runtime` inside it elsewhere. Unlike [`runtime_dir`](#tymethod.runtime_dir), this creates the fallback directory if it doesn’t exist yet, with `0700` permissions on Unix.
+    ///
+    /// # Security
+    /// The temporary directory is shared by all users on Unix, so another user could have created the fallback before you. An existing fallback is only reused if it is a directory rather than a symlink, and (on Unix) if it is owned by the [effective user](../fn.effective_uid.html) & isn’t accessible to anyone else; otherwise an error is returned.
+    /// The fallback also doesn’t meet the XDG spec’s other guarantees: it outlives the user’s session, and it may be cleaned up by the system at any time.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::collections::HashMap;
+    ///
+    /// let base_strategy = Xdg::new_with_env(HashMap::<String, String>::new()).unwrap();
+    /// assert_eq!(base_strategy.runtime_dir(), None);
+    ///
+    /// let runtime_dir = base_strategy.runtime_dir_or_fallback().unwrap();
+    /// assert!(runtime_dir.starts_with(std::env::temp_dir()));
+    /// assert!(runtime_dir.is_dir());
+    ///
+    /// #[cfg(unix)]
+    /// {
+    ///     use std::os::unix::fs::PermissionsExt;
+    ///     let mode = runtime_dir.metadata().unwrap().permissions().mode();
+    ///     assert_eq!(mode & 0o777, 0o700);
+    /// }
+    /// # }
+    /// ```
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        match self.runtime_dir() {
+            Some(runtime_dir) => Ok(runtime_dir),
+            None => fallback_runtime_dir("runtime"),
+        }
+    }
+
+    /// Gets the user’s log directory.
+    /// By default, this is `logs` inside the [state directory](#tymethod.state_dir), or `None` if there is none; the built-in strategies override it with their platform’s convention.
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.state_dir().map(|state_dir| state_dir.join("logs"))
+    }
+
+    /// Gets the user’s binary directory, where executables installed for the current user should be placed.
+    /// By default, this is `~/.local/bin/`; the built-in strategies override it with their platform’s convention.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// // A strategy only has to provide the directories which have no default.
+    /// struct Minimal(PathBuf);
+    ///
+    /// impl BaseStrategy for Minimal {
+    ///     fn home_dir(&self) -> &Path {
+    ///         &self.0
+    ///     }
+    ///     fn config_dir(&self) -> PathBuf {
+    ///         self.0.join("config")
+    ///     }
+    ///     fn data_dir(&self) -> PathBuf {
+    ///         self.0.join("data")
+    ///     }
+    ///     fn cache_dir(&self) -> PathBuf {
+    ///         self.0.join("cache")
+    ///     }
+    ///     fn state_dir(&self) -> Option<PathBuf> {
+    ///         Some(self.0.join("state"))
+    ///     }
+    ///     fn runtime_dir(&self) -> Option<PathBuf> {
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// let strategy = Minimal(PathBuf::from("/home/me"));
+    /// assert_eq!(strategy.bin_dir(), Path::new("/home/me/.local/bin/"));
+    /// assert_eq!(strategy.log_dir(), Some(PathBuf::from("/home/me/state/logs")));
+    /// ```
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir().join(".local/bin/")
+    }
+
+    /// Gets the conventional location to install executables for the current user to, e.g. for a `cargo install`-style tool placing its shims.
+    ///
+    /// This is the [`bin_dir`](#tymethod.bin_dir), e.g. `~/.local/bin/` on Linux or `%LOCALAPPDATA%\Programs` on Windows, unless it isn’t on the `PATH` but `~/bin/` is, in which case the latter is returned, as that is where the user’s shell will find the executables. `PATH` is read through [`env_var_os`](#method.env_var_os).
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::base_strategy::{BaseStrategy, Xdg};
+    /// use std::collections::HashMap;
+    ///
+    /// let base_strategy = Xdg::new_with_env(HashMap::from([("PATH", "")])).unwrap();
+    /// assert_eq!(base_strategy.user_executable_dir(), base_strategy.bin_dir());
+    ///
+    /// let home_bin = base_strategy.home_dir().join("bin");
+    /// let path = std::env::join_paths([&home_bin]).unwrap();
+    /// let base_strategy = Xdg::new_with_env(HashMap::from([("PATH", path)])).unwrap();
+    /// assert_eq!(base_strategy.user_executable_dir(), home_bin);
+    /// # }
+    /// ```
+    fn user_executable_dir(&self) -> PathBuf {
+        let bin_dir = self.bin_dir();
+        let home_bin_dir = self.home_dir().join("bin");
+
+        let Some(path) = self.env_var_os("PATH") else {
+            return bin_dir;
+        };
+        // `Path`’s equality compares components, so trailing separators don’t matter.
+        let is_on_path = |dir: &Path| std::env::split_paths(&path).any(|entry| entry == dir);
+
+        if !is_on_path(&bin_dir) && is_on_path(&home_bin_dir) {
+            home_bin_dir
+        } else {
+            bin_dir
+        }
+    }
+
+    /// Gets one of the user’s directories, selected by `kind`, e.g. to loop over [`DirKind::ALL`](../enum.DirKind.html#associatedconstant.ALL). This returns `None` for directories the strategy doesn’t have.
+    fn dir(&self, kind: DirKind) -> Option<PathBuf> {
+        match kind {
+            DirKind::Home => Some(self.home_dir().to_path_buf()),
+            DirKind::Config => Some(self.config_dir()),
+            DirKind::Data => Some(self.data_dir()),
+            DirKind::Cache => Some(self.cache_dir()),
+            DirKind::State => self.state_dir(),
+            DirKind::Runtime => self.runtime_dir(),
+            DirKind::Log => self.log_dir(),
+            DirKind::Bin => Some(self.bin_dir()),
+        }
+    }
+
+    /// Gets the user’s configuration directory along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        (self.config_dir(), DirSource::Default)
+    }
+
+    /// Gets the user’s data directory along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        (self.data_dir(), DirSource::Default)
+    }
+
+    /// Gets the user’s cache directory along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        (self.cache_dir(), DirSource::Default)
+    }
+
+    /// Gets the user’s state directory along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.state_dir().map(|dir| (dir, DirSource::Default))
+    }
+
+    /// Gets the user’s runtime directory along with where it came from.
+    /// Strategies which don’t read the environment report [`DirSource::Default`](../enum.DirSource.html#variant.Default).
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.runtime_dir().map(|dir| (dir, DirSource::Default))
+    }
+
+    /// Returns whether `path` lies inside the user’s home directory, e.g. to warn when `XDG_DATA_HOME` points at a shared mount.
+    ///
+    /// The comparison is lexical, so symlinks aren’t followed; use [`canonicalize_existing`](../fn.canonicalize_existing.html) on both sides first if that matters. To err on the side of caution, paths containing `..` are never considered to be inside the home directory.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::{BaseStrategy, Custom};
+    /// use std::path::Path;
+    ///
+    /// let (home_path, data_path) = if cfg!(windows) {
+    ///     ("C:\\home\\", "D:\\srv\\data\\")
+    /// } else {
+    ///     ("/home/", "/srv/data/")
+    /// };
+    ///
+    /// let base_strategy = Custom::builder(home_path).data_dir(data_path).build().unwrap();
+    ///
+    /// assert!(base_strategy.is_within_home(&base_strategy.config_dir()));
+    /// assert!(!base_strategy.is_within_home(&base_strategy.data_dir()));
+    /// assert!(!base_strategy.is_within_home(&Path::new(home_path).join("../srv")));
+    /// ```
+    fn is_within_home(&self, path: &Path) -> bool {
+        crate::is_within(path, self.home_dir())
+    }
+
+    /// Abbreviates `path` for display by replacing the user’s home directory with `~`, e.g. `~/.config/`. See [`tilde_abbreviate`](../fn.tilde_abbreviate.html) for more information.
+    fn tilde_abbreviate(&self, path: &Path) -> String {
+        crate::tilde_abbreviate(path, self.home_dir())
+    }
+
+    /// Gets [`config_dir`](#tymethod.config_dir), borrowing it from the strategy rather than allocating a new path if it is already stored, as it is by [`Cached`](../struct.Cached.html) once computed.
+    ///
+    /// This is meant for code which queries the directories in hot loops. Strategies which compute the directory on every call return an owned path.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::base_strategy::{BaseStrategy, Xdg};
+    /// use etcetera::Cached;
+    /// use std::borrow::Cow;
+    ///
+    /// let base_strategy = Cached::new(Xdg::new().unwrap());
+    ///
+    /// let config_dir = base_strategy.config_dir_cow();
+    /// assert!(matches!(config_dir, Cow::Borrowed(_)));
+    /// assert_eq!(config_dir, base_strategy.config_dir());
+    /// # }
+    /// ```
+    fn config_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Owned(self.config_dir())
+    }
+
+    /// Gets [`data_dir`](#tymethod.data_dir), borrowing it if possible. See [`config_dir_cow`](#method.config_dir_cow) for more information.
+    fn data_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Owned(self.data_dir())
+    }
+
+    /// Gets [`cache_dir`](#tymethod.cache_dir), borrowing it if possible. See [`config_dir_cow`](#method.config_dir_cow) for more information.
+    fn cache_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Owned(self.cache_dir())
+    }
+
+    /// Resolves every directory of the strategy at once.
+    fn snapshot(&self) -> ResolvedDirs {
+        ResolvedDirs {
+            home_dir: self.home_dir().to_path_buf(),
+            config_dir: self.config_dir(),
+            data_dir: self.data_dir(),
+            cache_dir: self.cache_dir(),
+            state_dir: self.state_dir(),
+            runtime_dir: self.runtime_dir(),
+            log_dir: self.log_dir(),
+            bin_dir: self.bin_dir(),
+        }
+    }
+
+    /// Finds out which of the user’s directories `path` lies in, e.g. to tell whether a file the user passed in is a cache entry.
+    /// This is the same as `self.snapshot().classify(path)`; see [`ResolvedDirs::classify`](../struct.ResolvedDirs.html#method.classify) for more information.
+    fn classify(&self, path: &Path) -> Option<DirClassification> {
+        self.snapshot().classify(path)
+    }
+
+    /// Gets the user’s configuration directory with its symlinks resolved. See [`canonicalize_existing`](../fn.canonicalize_existing.html) for more information.
+    fn canonical_config_dir(&self) -> io::Result<PathBuf> {
+        crate::canonicalize_existing(self.config_dir())
+    }
+
+    /// Gets the user’s data directory with its symlinks resolved. See [`canonicalize_existing`](../fn.canonicalize_existing.html) for more information.
+    fn canonical_data_dir(&self) -> io::Result<PathBuf> {
+        crate::canonicalize_existing(self.data_dir())
+    }
+
+    /// Gets the user’s cache directory with its symlinks resolved. See [`canonicalize_existing`](../fn.canonicalize_existing.html) for more information.
+    fn canonical_cache_dir(&self) -> io::Result<PathBuf> {
+        crate::canonicalize_existing(self.cache_dir())
+    }
+
+    /// Checks whether the user’s configuration directory exists, without creating it, e.g. to decide whether to run a first-time setup.
+    ///
+    /// Like [`Path::try_exists`], this returns an error if existence can neither be confirmed nor denied, e.g. because a parent directory isn’t readable, rather than pretending the directory is missing. Broken symlinks count as missing.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::{BaseStrategy, Custom};
+    ///
+    /// let home_dir = std::env::temp_dir().join(format!("etcetera-dir-exists-{}", std::process::id()));
+    /// let base_strategy = Custom::builder(&home_dir).build().unwrap();
+    ///
+    /// assert!(!base_strategy.config_dir_exists().unwrap());
+    /// std::fs::create_dir_all(base_strategy.config_dir()).unwrap();
+    /// assert!(base_strategy.config_dir_exists().unwrap());
+    ///
+    /// // This strategy has no state directory.
+    /// assert!(!base_strategy.state_dir_exists().unwrap());
+    ///
+    /// std::fs::remove_dir_all(&home_dir).unwrap();
+    /// ```
+    fn config_dir_exists(&self) -> io::Result<bool> {
+        self.config_dir().try_exists()
+    }
+
+    /// Checks whether the user’s data directory exists, without creating it. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn data_dir_exists(&self) -> io::Result<bool> {
+        self.data_dir().try_exists()
+    }
+
+    /// Checks whether the user’s cache directory exists, without creating it. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn cache_dir_exists(&self) -> io::Result<bool> {
+        self.cache_dir().try_exists()
+    }
+
+    /// Checks whether the user’s state directory exists, without creating it. This returns `false` if there is no state directory at all. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn state_dir_exists(&self) -> io::Result<bool> {
+        self.state_dir().map_or(Ok(false), |dir| dir.try_exists())
+    }
+
+    /// Checks whether the user’s runtime directory exists, without creating it. This returns `false` if there is no runtime directory at all. See [`config_dir_exists`](#method.config_dir_exists) for more information.
+    fn runtime_dir_exists(&self) -> io::Result<bool> {
+        self.runtime_dir().map_or(Ok(false), |dir| dir.try_exists())
+    }
+
+    #[cfg(feature = "camino")]
+    utf8_methods!(
+        config_dir_utf8 => config_dir,
+        data_dir_utf8 => data_dir,
+        cache_dir_utf8 => cache_dir,
+        state_dir_utf8 => state_dir,
+        runtime_dir_utf8 => runtime_dir,
+        log_dir_utf8 => log_dir,
+        bin_dir_utf8 => bin_dir,
+    );
+
+    /// Gets the user’s “Desktop” folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets the user’s “Documents” folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn documents_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets the user’s “Downloads” folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn downloads_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets the user’s “Music” folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn music_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets the user’s “Pictures” folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets the user’s public share folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn public_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets the user’s “Templates” folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn templates_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Gets the user’s “Videos” folder.
+    /// This returns `None` if the strategy doesn’t know about it.
+    fn videos_dir(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+// Creates `<prefix>-<uid>` (or just `<prefix>` outside of Unix) inside the temporary directory, or checks that an existing one is safe to reuse.
+pub(crate) fn fallback_runtime_dir(prefix: &str) -> io::Result<PathBuf> {
+    if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "there is no temporary directory on this platform",
+        ));
+    }
+
+    #[cfg(unix)]
+    let name = format!("{prefix}-{}", crate::effective_uid());
+    #[cfg(not(unix))]
+    let name = prefix;
+
+    let path = std::env::temp_dir().join(name);
+    create_private_dir(&path)?;
+    Ok(path)
+}
+
+// Creates the folder `path` in a location shared with other users, or checks that an existing one is safe to reuse, i.e. that it is a real folder which, on Unix, only the current user can access.
+pub(crate) fn create_private_dir(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    let created = {
+        use std::os::unix::fs::DirBuilderExt;
+        std::fs::DirBuilder::new().mode(0o700).create(path)
+    };
+    #[cfg(not(unix))]
+    let created = std::fs::create_dir(path);
+
+    match created {
+        Ok(()) => return Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(err) => return Err(err),
+    }
+
+    // `symlink_metadata` doesn’t follow symlinks, so a symlink planted by someone else is rejected here.
+    let metadata = std::fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists but isn’t a directory", path.display()),
+        ));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        if metadata.uid() != crate::effective_uid() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is owned by another user", path.display()),
+            ));
+        }
+        if metadata.permissions().mode() & 0o077 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is accessible to other users", path.display()),
+            ));
+        }
+    }
+
+    Ok(())
 }
 
 macro_rules! create_strategies {
-    ($native: ty, $base: ty) => {
+    ($native: ident, $base: ident) => {
         /// Returns the current OS’s native [`BaseStrategy`](trait.BaseStrategy.html).
-        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, [`Apple`](struct.Apple.html) on macOS & iOS, and [`Xdg`](struct.Xdg.html) everywhere else.
+        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, [`Apple`](struct.Apple.html) on macOS & iOS, [`InMemory`](struct.InMemory.html) on `wasm32-unknown-unknown`, and [`Xdg`](struct.Xdg.html) everywhere else.
         /// This is the convention used by most GUI applications.
         pub fn choose_native_strategy() -> Result<$native, HomeDirError> {
-            <$native>::new()
+            create_strategies!(@new $native)
         }
 
         /// Returns the current OS’s default [`BaseStrategy`](trait.BaseStrategy.html).
-        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, and [`Xdg`](struct.Xdg.html) everywhere else.
+        /// This uses the [`Windows`](struct.Windows.html) strategy on Windows, [`InMemory`](struct.InMemory.html) on `wasm32-unknown-unknown`, and [`Xdg`](struct.Xdg.html) everywhere else.
         /// This is the convention used by most CLI applications.
         pub fn choose_base_strategy() -> Result<$base, HomeDirError> {
-            <$base>::new()
+            create_strategies!(@new $base)
+        }
+    };
+    // Without a home directory, `InMemory` is rooted at `/`. Elsewhere, it is only used if every other strategy is disabled.
+    (@new InMemory) => {
+        if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+            Ok(InMemory::new())
+        } else {
+            crate::home_dir().and_then(InMemory::with_root_dir)
         }
     };
+    (@new $strategy: ident) => {
+        $strategy::new()
+    };
 }
 
+// If the feature of a strategy is disabled, the next one that fits is used. See the “Cargo Features” section of the crate documentation.
 cfg_if::cfg_if! {
-    if #[cfg(target_os = "windows")] {
+    if #[cfg(all(target_os = "windows", feature = "windows"))] {
         create_strategies!(Windows, Windows);
-    } else if #[cfg(any(target_os = "macos", target_os = "ios"))] {
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple", feature = "xdg"))] {
         create_strategies!(Apple, Xdg);
-    } else {
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple"))] {
+        create_strategies!(Apple, Apple);
+    } else if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
+        create_strategies!(InMemory, InMemory);
+    } else if #[cfg(feature = "xdg")] {
         create_strategies!(Xdg, Xdg);
+    } else {
+        create_strategies!(InMemory, InMemory);
     }
 }
 
+mod any;
+#[cfg(feature = "apple")]
 mod apple;
+mod custom;
+#[cfg(feature = "xdg")]
+mod flatpak;
+mod in_memory;
+mod portable;
+#[cfg(feature = "xdg")]
+mod snap;
+#[cfg(feature = "windows")]
 mod windows;
+#[cfg(feature = "xdg")]
 mod xdg;
 
+pub(crate) use any::strategy_override;
+pub use any::{
+    choose_base_strategy_enum, choose_base_strategy_or_fallback, choose_native_strategy_enum,
+    AnyBaseStrategy,
+};
+#[cfg(feature = "apple")]
 pub use apple::Apple;
+pub use custom::{Custom, CustomBuilder};
+#[cfg(feature = "xdg")]
+pub use flatpak::Flatpak;
+pub use in_memory::InMemory;
+pub use portable::Portable;
+#[cfg(feature = "xdg")]
+pub use snap::Snap;
+#[cfg(feature = "windows")]
 pub use windows::Windows;
+#[cfg(feature = "xdg")]
 pub use xdg::Xdg;
@@ -0,0 +1,246 @@
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "apple")]
+use super::Apple;
+#[cfg(feature = "windows")]
+use super::Windows;
+#[cfg(feature = "xdg")]
+use super::Xdg;
+use super::{BaseStrategy, InMemory};
+use crate::{DirSource, HomeDirError, ResolvedDirs, StrategyKind};
+
+/// Any of the base strategies `choose_*` can return, chosen at runtime without boxing.
+///
+/// This implements [`BaseStrategy`](trait.BaseStrategy.html) by forwarding every method to the strategy it holds. It is returned by [`choose_native_strategy_enum`](fn.choose_native_strategy_enum.html) & [`choose_base_strategy_enum`](fn.choose_base_strategy_enum.html), which can pick a different strategy than the one for the current OS.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum AnyBaseStrategy {
+    /// The [`Apple`](struct.Apple.html) strategy.
+    #[cfg(feature = "apple")]
+    Apple(Apple),
+    /// The [`InMemory`](struct.InMemory.html) strategy.
+    InMemory(InMemory),
+    /// The [`Windows`](struct.Windows.html) strategy.
+    #[cfg(feature = "windows")]
+    Windows(Windows),
+    /// The [`Xdg`](struct.Xdg.html) strategy.
+    #[cfg(feature = "xdg")]
+    Xdg(Xdg),
+}
+
+macro_rules! delegate {
+    ($($name: ident -> $ret: ty),* $(,)?) => {
+        $(
+            fn $name(&self) -> $ret {
+                match self {
+                    #[cfg(feature = "apple")]
+                    Self::Apple(strategy) => strategy.$name(),
+                    Self::InMemory(strategy) => strategy.$name(),
+                    #[cfg(feature = "windows")]
+                    Self::Windows(strategy) => strategy.$name(),
+                    #[cfg(feature = "xdg")]
+                    Self::Xdg(strategy) => strategy.$name(),
+                }
+            }
+        )*
+    };
+}
+
+impl BaseStrategy for AnyBaseStrategy {
+    fn home_dir(&self) -> &Path {
+        match self {
+            #[cfg(feature = "apple")]
+            Self::Apple(strategy) => strategy.home_dir(),
+            Self::InMemory(strategy) => strategy.home_dir(),
+            #[cfg(feature = "windows")]
+            Self::Windows(strategy) => strategy.home_dir(),
+            #[cfg(feature = "xdg")]
+            Self::Xdg(strategy) => strategy.home_dir(),
+        }
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        match self {
+            #[cfg(feature = "apple")]
+            Self::Apple(strategy) => strategy.env_var_os(key),
+            Self::InMemory(strategy) => strategy.env_var_os(key),
+            #[cfg(feature = "windows")]
+            Self::Windows(strategy) => strategy.env_var_os(key),
+            #[cfg(feature = "xdg")]
+            Self::Xdg(strategy) => strategy.env_var_os(key),
+        }
+    }
+
+    delegate!(
+        config_dir -> PathBuf,
+        data_dir -> PathBuf,
+        local_data_dir -> PathBuf,
+        cache_dir -> PathBuf,
+        state_dir -> Option<PathBuf>,
+        runtime_dir -> Option<PathBuf>,
+        runtime_dir_or_fallback -> io::Result<PathBuf>,
+        log_dir -> Option<PathBuf>,
+        bin_dir -> PathBuf,
+        config_dir_with_source -> (PathBuf, DirSource),
+        data_dir_with_source -> (PathBuf, DirSource),
+        cache_dir_with_source -> (PathBuf, DirSource),
+        state_dir_with_source -> Option<(PathBuf, DirSource)>,
+        runtime_dir_with_source -> Option<(PathBuf, DirSource)>,
+        snapshot -> ResolvedDirs,
+        config_dir_cow -> Cow<'_, Path>,
+        data_dir_cow -> Cow<'_, Path>,
+        cache_dir_cow -> Cow<'_, Path>,
+        canonical_config_dir -> io::Result<PathBuf>,
+        canonical_data_dir -> io::Result<PathBuf>,
+        canonical_cache_dir -> io::Result<PathBuf>,
+        desktop_dir -> Option<PathBuf>,
+        documents_dir -> Option<PathBuf>,
+        downloads_dir -> Option<PathBuf>,
+        music_dir -> Option<PathBuf>,
+        pictures_dir -> Option<PathBuf>,
+        public_dir -> Option<PathBuf>,
+        templates_dir -> Option<PathBuf>,
+        videos_dir -> Option<PathBuf>,
+    );
+}
+
+macro_rules! impl_from {
+    ($($strategy: ident),*) => {
+        $(
+            impl From<$strategy> for AnyBaseStrategy {
+                fn from(strategy: $strategy) -> Self {
+                    Self::$strategy(strategy)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "apple")]
+impl_from!(Apple);
+impl_from!(InMemory);
+#[cfg(feature = "windows")]
+impl_from!(Windows);
+#[cfg(feature = "xdg")]
+impl_from!(Xdg);
+
+/// The environment variable which forces the `choose_*_enum` functions to return a specific strategy.
+pub(crate) const STRATEGY_ENV_VAR: &str = "ETCETERA_STRATEGY";
+
+// Reads `ETCETERA_STRATEGY`, ignoring case & surrounding whitespace. Unknown values are ignored.
+pub(crate) fn strategy_override() -> Option<StrategyKind> {
+    let name = std::env::var_os(STRATEGY_ENV_VAR)?.into_string().ok()?;
+    name.trim().parse().ok()
+}
+
+fn choose_overridden(
+    default: impl FnOnce() -> Result<AnyBaseStrategy, HomeDirError>,
+) -> Result<AnyBaseStrategy, HomeDirError> {
+    match strategy_override() {
+        #[cfg(feature = "apple")]
+        Some(StrategyKind::Apple) => Apple::new().map(AnyBaseStrategy::Apple),
+        #[cfg(feature = "windows")]
+        Some(StrategyKind::Windows) => Windows::new().map(AnyBaseStrategy::Windows),
+        #[cfg(feature = "xdg")]
+        Some(StrategyKind::Xdg) => Xdg::new().map(AnyBaseStrategy::Xdg),
+        // There is no Unix base strategy.
+        #[cfg(feature = "unix")]
+        Some(StrategyKind::Unix) => default(),
+        None => default(),
+    }
+}
+
+/// Returns the same strategy as [`choose_base_strategy_enum`](fn.choose_base_strategy_enum.html), or a best-effort stand-in if it cannot be created, e.g. because there is no home directory in a minimal container. This never fails, so that your application can still start in a degraded environment.
+///
+/// The strategies are tried in this order:
+/// 1. The strategy `choose_base_strategy_enum` returns.
+/// 2. An [`InMemory`](struct.InMemory.html) strategy rooted at a private `etcetera-home-<uid>` folder inside the temporary directory, which is created with the same safety checks as the fallback of [`runtime_dir_or_fallback`](trait.BaseStrategy.html#method.runtime_dir_or_fallback). Outside of Unix, the folder is just called `etcetera-home`.
+/// 3. An `InMemory` strategy rooted at `/`, which is what `wasm32-unknown-unknown` always gets.
+///
+/// As the temporary directory may be cleared at any time, you may want to warn your users when you get anything but the first strategy.
+///
+/// ```
+/// use etcetera::base_strategy::{choose_base_strategy_enum, choose_base_strategy_or_fallback};
+///
+/// let base_strategy = choose_base_strategy_or_fallback();
+///
+/// if let Ok(preferred) = choose_base_strategy_enum() {
+///     assert_eq!(base_strategy, preferred);
+/// }
+/// ```
+pub fn choose_base_strategy_or_fallback() -> AnyBaseStrategy {
+    choose_base_strategy_enum().unwrap_or_else(|_| {
+        let in_memory = super::fallback_runtime_dir("etcetera-home")
+            .ok()
+            .and_then(|root_dir| InMemory::with_root_dir(root_dir).ok())
+            .unwrap_or_default();
+        AnyBaseStrategy::InMemory(in_memory)
+    })
+}
+
+macro_rules! create_strategies {
+    ($native: ident, $base: ident) => {
+        /// Returns the current OS’s native [`BaseStrategy`](trait.BaseStrategy.html), wrapped in an [`AnyBaseStrategy`](enum.AnyBaseStrategy.html).
+        /// See [`choose_native_strategy`](fn.choose_native_strategy.html) for which strategy this is. See [`choose_base_strategy_enum`](fn.choose_base_strategy_enum.html) for how to override it.
+        pub fn choose_native_strategy_enum() -> Result<AnyBaseStrategy, HomeDirError> {
+            choose_overridden(|| create_strategies!(@new $native).map(AnyBaseStrategy::$native))
+        }
+
+        /// Returns the current OS’s default [`BaseStrategy`](trait.BaseStrategy.html), wrapped in an [`AnyBaseStrategy`](enum.AnyBaseStrategy.html).
+        /// See [`choose_base_strategy`](fn.choose_base_strategy.html) for which strategy this is.
+        ///
+        /// Unlike `choose_base_strategy`, whose return type is fixed at compile time, this honours the `ETCETERA_STRATEGY` environment variable: set it to `xdg`, `windows` or `apple` to get that strategy on any OS, e.g. to test the Windows layout on Linux, or to let power users pick the layout they prefer. Any other value is ignored.
+        ///
+        /// ```
+        /// # #[cfg(feature = "windows")]
+        /// # {
+        /// use etcetera::base_strategy::{choose_base_strategy_enum, AnyBaseStrategy, BaseStrategy, Windows};
+        ///
+        /// std::env::set_var("ETCETERA_STRATEGY", "windows");
+        /// let base_strategy = choose_base_strategy_enum().unwrap();
+        /// assert!(matches!(base_strategy, AnyBaseStrategy::Windows(_)));
+        /// assert_eq!(base_strategy.config_dir(), Windows::new().unwrap().config_dir());
+        ///
+        /// std::env::set_var("ETCETERA_STRATEGY", "beos");
+        /// assert_eq!(
+        ///     choose_base_strategy_enum().unwrap().config_dir(),
+        ///     etcetera::choose_base_strategy().unwrap().config_dir()
+        /// );
+        /// # }
+        /// ```
+        pub fn choose_base_strategy_enum() -> Result<AnyBaseStrategy, HomeDirError> {
+            choose_overridden(|| create_strategies!(@new $base).map(AnyBaseStrategy::$base))
+        }
+    };
+    // Without a home directory, `InMemory` is rooted at `/`. Elsewhere, it is only used if every other strategy is disabled.
+    (@new InMemory) => {
+        if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+            Ok(InMemory::new())
+        } else {
+            crate::home_dir().and_then(InMemory::with_root_dir)
+        }
+    };
+    (@new $strategy: ident) => {
+        $strategy::new()
+    };
+}
+
+// If the feature of a strategy is disabled, the next one that fits is used. See the “Cargo Features” section of the crate documentation.
+cfg_if::cfg_if! {
+    if #[cfg(all(target_os = "windows", feature = "windows"))] {
+        create_strategies!(Windows, Windows);
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple", feature = "xdg"))] {
+        create_strategies!(Apple, Xdg);
+    } else if #[cfg(all(any(target_os = "macos", target_os = "ios"), feature = "apple"))] {
+        create_strategies!(Apple, Apple);
+    } else if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
+        create_strategies!(InMemory, InMemory);
+    } else if #[cfg(feature = "xdg")] {
+        create_strategies!(Xdg, Xdg);
+    } else {
+        create_strategies!(InMemory, InMemory);
+    }
+}
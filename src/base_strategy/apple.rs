@@ -1,9 +1,11 @@
 use std::path::{Path, PathBuf};
 
-use crate::HomeDirError;
+use crate::{CreationError, HomeDirError};
 
 /// This is the strategy created by Apple for use on macOS and iOS devices. It is always used by GUI apps on macOS, and is sometimes used by command-line applications there too. iOS only has GUIs, so all iOS applications follow this strategy. The specification is available [here](https://developer.apple.com/library/archive/documentation/FileManagement/Conceptual/FileSystemProgrammingGuide/FileSystemOverview/FileSystemOverview.html#//apple_ref/doc/uid/TP40010672-CH2-SW1).
 ///
+/// Apple’s platforms have no dedicated state folder, so the state directory is `~/Library/Application Support/`, just like the data directory.
+///
 /// ```
 /// use etcetera::base_strategy::Apple;
 /// use etcetera::base_strategy::BaseStrategy;
@@ -30,13 +32,33 @@ use crate::HomeDirError;
 ///     Ok(Path::new("Library/Caches/"))
 /// );
 /// assert_eq!(
-///     base_strategy.state_dir(),
-///     None
+///     base_strategy.state_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Library/Application Support/"))
 /// );
 /// assert_eq!(
 ///     base_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     base_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Library/Logs/"))
+/// );
+/// assert_eq!(
+///     base_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/bin/"))
+/// );
+/// assert_eq!(
+///     base_strategy.documents_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Documents/"))
+/// );
+/// assert_eq!(
+///     base_strategy.videos_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Movies/"))
+/// );
+/// assert_eq!(
+///     base_strategy.templates_dir(),
+///     None
+/// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Apple {
@@ -49,6 +71,56 @@ impl Apple {
             home_dir: crate::home_dir()?,
         })
     }
+
+    /// Create a new Apple BaseStrategy using the given home directory instead of the current user’s one.
+    /// This returns an error if `home_dir` isn’t absolute.
+    pub fn with_home_dir(home_dir: PathBuf) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            home_dir: crate::validate_home_dir(home_dir)?,
+        })
+    }
+
+    /// Gets the folder shared by the apps & extensions of the App Group `group_id`, i.e. `~/Library/Group Containers/<group_id>/`, e.g. to share state between an app and its helpers.
+    ///
+    /// `group_id` is one of the identifiers listed in the `com.apple.security.application-groups` entitlement of your apps, which is your Team ID followed by a dot & a name of your choice on macOS, e.g. `ABCDE12345.com.example.frobnicator`, or `group.` followed by a reverse-DNS name, e.g. `group.com.example.frobnicator`, which iOS requires. It is used as the folder name as-is, so this returns an error if it cannot be one, following the same rules as [`AppStrategyArgs::validate`](../app_strategy/struct.AppStrategyArgs.html#method.validate), e.g. if it contains a path separator or is `..`. macOS creates the folder for apps which have the entitlement; the path is only computed here.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Apple;
+    /// use etcetera::CreationError;
+    /// use std::path::Path;
+    ///
+    /// let base_strategy = Apple::new().unwrap();
+    ///
+    /// let home_dir = etcetera::home_dir().unwrap();
+    ///
+    /// assert_eq!(
+    ///     base_strategy
+    ///         .group_container_dir("ABCDE12345.com.example.frobnicator")
+    ///         .unwrap()
+    ///         .strip_prefix(&home_dir),
+    ///     Ok(Path::new("Library/Group Containers/ABCDE12345.com.example.frobnicator/"))
+    /// );
+    ///
+    /// for group_id in ["", "..", "/etc", "group.com.example/../../x"] {
+    ///     assert!(matches!(
+    ///         base_strategy.group_container_dir(group_id),
+    ///         Err(CreationError::InvalidGroupId { .. })
+    ///     ));
+    /// }
+    /// ```
+    pub fn group_container_dir(&self, group_id: &str) -> Result<PathBuf, CreationError> {
+        if let Err(reason) = crate::app_strategy::validate_component(group_id) {
+            return Err(CreationError::InvalidGroupId {
+                group_id: group_id.to_string(),
+                reason,
+            });
+        }
+
+        Ok(self
+            .home_dir
+            .join("Library/Group Containers/")
+            .join(group_id))
+    }
 }
 
 impl super::BaseStrategy for Apple {
@@ -69,10 +141,46 @@ impl super::BaseStrategy for Apple {
     }
 
     fn state_dir(&self) -> Option<PathBuf> {
-        None
+        Some(self.data_dir())
     }
 
     fn runtime_dir(&self) -> Option<PathBuf> {
         None
     }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Library/Logs/"))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir.join(".local/bin/")
+    }
+
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Desktop/"))
+    }
+
+    fn documents_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Documents/"))
+    }
+
+    fn downloads_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Downloads/"))
+    }
+
+    fn music_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Music/"))
+    }
+
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Pictures/"))
+    }
+
+    fn public_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Public/"))
+    }
+
+    fn videos_dir(&self) -> Option<PathBuf> {
+        Some(self.home_dir.join("Movies/"))
+    }
 }
@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use crate::HomeDirError;
+
+/// This strategy returns exactly the directories you give it, for deployments which none of the other strategies fit, e.g. unusual container layouts, or tests.
+///
+/// It is created through a [`CustomBuilder`](struct.CustomBuilder.html). The configuration, data, cache & binary directories default to the XDG defaults inside the home directory, while the state, runtime & log directories default to `None`.
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::Custom;
+/// use std::path::Path;
+///
+/// let (home_path, config_path, state_path) = if cfg!(windows) {
+///     ("C:\\home\\", "C:\\etc\\frobnicator\\", "C:\\var\\lib\\frobnicator\\")
+/// } else {
+///     ("/home/", "/etc/frobnicator/", "/var/lib/frobnicator/")
+/// };
+///
+/// let base_strategy = Custom::builder(home_path)
+///     .config_dir(config_path)
+///     .state_dir(state_path)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(base_strategy.home_dir(), Path::new(home_path));
+/// assert_eq!(base_strategy.config_dir(), Path::new(config_path));
+/// assert_eq!(base_strategy.data_dir(), Path::new(home_path).join(".local/share/"));
+/// assert_eq!(base_strategy.cache_dir(), Path::new(home_path).join(".cache/"));
+/// assert_eq!(base_strategy.state_dir().as_deref(), Some(Path::new(state_path)));
+/// assert_eq!(base_strategy.runtime_dir(), None);
+/// assert_eq!(base_strategy.log_dir(), None);
+/// assert_eq!(base_strategy.bin_dir(), Path::new(home_path).join(".local/bin/"));
+///
+/// // The home directory must be absolute.
+/// assert!(Custom::builder("home").build().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Custom {
+    home_dir: PathBuf,
+    config_dir: PathBuf,
+    data_dir: PathBuf,
+    cache_dir: PathBuf,
+    state_dir: Option<PathBuf>,
+    runtime_dir: Option<PathBuf>,
+    log_dir: Option<PathBuf>,
+    bin_dir: PathBuf,
+}
+
+impl Custom {
+    /// Starts building a Custom BaseStrategy with the given home directory.
+    pub fn builder(home_dir: impl Into<PathBuf>) -> CustomBuilder {
+        CustomBuilder {
+            home_dir: home_dir.into(),
+            config_dir: None,
+            data_dir: None,
+            cache_dir: None,
+            state_dir: None,
+            runtime_dir: None,
+            log_dir: None,
+            bin_dir: None,
+        }
+    }
+}
+
+/// Builds a [`Custom`](struct.Custom.html) strategy. Every directory you don’t set gets its default.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomBuilder {
+    home_dir: PathBuf,
+    config_dir: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    state_dir: Option<PathBuf>,
+    runtime_dir: Option<PathBuf>,
+    log_dir: Option<PathBuf>,
+    bin_dir: Option<PathBuf>,
+}
+
+macro_rules! setter {
+    ($($name: ident: $what: literal),* $(,)?) => {
+        $(
+            #[doc = concat!("Sets the ", $what, " directory.")]
+            pub fn $name(mut self, $name: impl Into<PathBuf>) -> Self {
+                self.$name = Some($name.into());
+                self
+            }
+        )*
+    };
+}
+
+impl CustomBuilder {
+    setter!(
+        config_dir: "configuration",
+        data_dir: "data",
+        cache_dir: "cache",
+        state_dir: "state",
+        runtime_dir: "runtime",
+        log_dir: "log",
+        bin_dir: "binary",
+    );
+
+    /// Creates the Custom BaseStrategy.
+    /// This returns an error if the home directory isn’t absolute.
+    pub fn build(self) -> Result<Custom, HomeDirError> {
+        let home_dir = crate::validate_home_dir(self.home_dir)?;
+
+        Ok(Custom {
+            config_dir: self.config_dir.unwrap_or_else(|| home_dir.join(".config/")),
+            data_dir: self
+                .data_dir
+                .unwrap_or_else(|| home_dir.join(".local/share/")),
+            cache_dir: self.cache_dir.unwrap_or_else(|| home_dir.join(".cache/")),
+            state_dir: self.state_dir,
+            runtime_dir: self.runtime_dir,
+            log_dir: self.log_dir,
+            bin_dir: self.bin_dir.unwrap_or_else(|| home_dir.join(".local/bin/")),
+            home_dir,
+        })
+    }
+}
+
+impl super::BaseStrategy for Custom {
+    fn home_dir(&self) -> &Path {
+        &self.home_dir
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.config_dir.clone()
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.data_dir.clone()
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.cache_dir.clone()
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.state_dir.clone()
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.runtime_dir.clone()
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.log_dir.clone()
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.bin_dir.clone()
+    }
+
+    fn config_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Borrowed(&self.config_dir)
+    }
+
+    fn data_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Borrowed(&self.data_dir)
+    }
+
+    fn cache_dir_cow(&self) -> Cow<'_, Path> {
+        Cow::Borrowed(&self.cache_dir)
+    }
+}
@@ -0,0 +1,206 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::env::{EnvProvider, StdEnv};
+use crate::{DirSource, HomeDirError};
+
+use super::{BaseStrategy, Xdg};
+
+/// This strategy is meant for applications distributed through [Flatpak](https://flatpak.org). Inside the sandbox, Flatpak points the XDG environment variables at `~/.var/app/<app-id>/`, so the regular directories are resolved exactly like the [`Xdg`](struct.Xdg.html) strategy does. Outside of a sandbox, this strategy behaves just like `Xdg`.
+///
+/// The host’s own directories are available through the `host_*_dir` methods, which read the `HOST_XDG_*` variables Flatpak exposes and fall back to the XDG defaults.
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::Flatpak;
+/// use std::collections::HashMap;
+/// use std::path::Path;
+///
+/// let home_dir = etcetera::home_dir().unwrap();
+/// let sandbox_config = home_dir.join(".var/app/org.acme-corp.Frobnicator/config");
+///
+/// let base_strategy = Flatpak::new_with_env(HashMap::from([
+///     ("FLATPAK_ID", "org.acme-corp.Frobnicator".into()),
+///     ("XDG_CONFIG_HOME", sandbox_config.clone().into_os_string()),
+/// ])).unwrap();
+///
+/// assert!(base_strategy.is_sandboxed());
+/// assert_eq!(base_strategy.app_id().as_deref(), Some("org.acme-corp.Frobnicator"));
+/// assert_eq!(base_strategy.config_dir(), sandbox_config);
+/// assert_eq!(
+///     base_strategy.host_config_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".config/"))
+/// );
+/// assert_eq!(
+///     base_strategy.host_data_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/share/"))
+/// );
+/// assert_eq!(
+///     base_strategy.host_cache_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".cache/"))
+/// );
+/// assert_eq!(
+///     base_strategy.host_state_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/state/"))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Flatpak<E = StdEnv> {
+    xdg: Xdg<E>,
+    sandboxed: bool,
+}
+
+impl Flatpak {
+    /// Create a new Flatpak BaseStrategy
+    pub fn new() -> Result<Self, HomeDirError> {
+        Self::new_with_env(StdEnv)
+    }
+}
+
+impl<E: EnvProvider> Flatpak<E> {
+    /// Create a new Flatpak BaseStrategy which reads the environment variables from `env` instead of the process’ environment.
+    pub fn new_with_env(env: E) -> Result<Self, HomeDirError> {
+        let xdg = Xdg::new_with_env(env)?;
+        // Flatpak always sets `FLATPAK_ID` and mounts `/.flatpak-info` inside the sandbox.
+        let sandboxed =
+            xdg.env().var_os("FLATPAK_ID").is_some() || Path::new("/.flatpak-info").is_file();
+
+        Ok(Self { xdg, sandboxed })
+    }
+
+    /// Returns whether the application is running inside a Flatpak sandbox.
+    pub fn is_sandboxed(&self) -> bool {
+        self.sandboxed
+    }
+
+    /// Gets the Flatpak application ID from `FLATPAK_ID`, if it is set.
+    pub fn app_id(&self) -> Option<String> {
+        self.xdg
+            .env()
+            .var_os("FLATPAK_ID")
+            .and_then(|id| id.into_string().ok())
+    }
+
+    fn host_dir(&self, host_env_var: &str, env_var: &str, default: &str) -> PathBuf {
+        let env_var = if self.sandboxed {
+            host_env_var
+        } else {
+            env_var
+        };
+        self.xdg.env_var_or_default(env_var, default)
+    }
+
+    /// Gets the host’s configuration directory, i.e. `HOST_XDG_CONFIG_HOME` or `~/.config/`.
+    /// Outside of a sandbox, this is the same as [`config_dir`](trait.BaseStrategy.html#tymethod.config_dir).
+    pub fn host_config_dir(&self) -> PathBuf {
+        self.host_dir("HOST_XDG_CONFIG_HOME", "XDG_CONFIG_HOME", ".config/")
+    }
+
+    /// Gets the host’s data directory, i.e. `HOST_XDG_DATA_HOME` or `~/.local/share/`.
+    /// Outside of a sandbox, this is the same as [`data_dir`](trait.BaseStrategy.html#tymethod.data_dir).
+    pub fn host_data_dir(&self) -> PathBuf {
+        self.host_dir("HOST_XDG_DATA_HOME", "XDG_DATA_HOME", ".local/share/")
+    }
+
+    /// Gets the host’s cache directory, i.e. `HOST_XDG_CACHE_HOME` or `~/.cache/`.
+    /// Outside of a sandbox, this is the same as [`cache_dir`](trait.BaseStrategy.html#tymethod.cache_dir).
+    pub fn host_cache_dir(&self) -> PathBuf {
+        self.host_dir("HOST_XDG_CACHE_HOME", "XDG_CACHE_HOME", ".cache/")
+    }
+
+    /// Gets the host’s state directory, i.e. `HOST_XDG_STATE_HOME` or `~/.local/state/`.
+    /// Outside of a sandbox, this is the same as [`state_dir`](trait.BaseStrategy.html#tymethod.state_dir).
+    pub fn host_state_dir(&self) -> PathBuf {
+        self.host_dir("HOST_XDG_STATE_HOME", "XDG_STATE_HOME", ".local/state/")
+    }
+}
+
+impl<E: EnvProvider> BaseStrategy for Flatpak<E> {
+    fn home_dir(&self) -> &Path {
+        self.xdg.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.xdg.env_var_os(key)
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.xdg.config_dir()
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.xdg.data_dir()
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.xdg.cache_dir()
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.xdg.state_dir()
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.xdg.runtime_dir()
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.xdg.log_dir()
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.xdg.bin_dir()
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.xdg.config_dir_with_source()
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.xdg.data_dir_with_source()
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.xdg.cache_dir_with_source()
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.xdg.state_dir_with_source()
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.xdg.runtime_dir_with_source()
+    }
+
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        self.xdg.desktop_dir()
+    }
+
+    fn documents_dir(&self) -> Option<PathBuf> {
+        self.xdg.documents_dir()
+    }
+
+    fn downloads_dir(&self) -> Option<PathBuf> {
+        self.xdg.downloads_dir()
+    }
+
+    fn music_dir(&self) -> Option<PathBuf> {
+        self.xdg.music_dir()
+    }
+
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        self.xdg.pictures_dir()
+    }
+
+    fn public_dir(&self) -> Option<PathBuf> {
+        self.xdg.public_dir()
+    }
+
+    fn templates_dir(&self) -> Option<PathBuf> {
+        self.xdg.templates_dir()
+    }
+
+    fn videos_dir(&self) -> Option<PathBuf> {
+        self.xdg.videos_dir()
+    }
+}
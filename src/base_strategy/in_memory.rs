@@ -0,0 +1,138 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::HomeDirError;
+
+/// This strategy never touches the OS: every directory is derived from a root folder, `/` by default, which stands in for the home directory. It follows the defaults of the [`Xdg`](struct.Xdg.html) strategy, without reading any environment variables.
+///
+/// It is meant for targets without a home directory, such as `wasm32-unknown-unknown`, where the `choose_*` functions return it, and for virtual filesystems.
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::InMemory;
+/// use std::path::Path;
+///
+/// let base_strategy = InMemory::new();
+///
+/// assert_eq!(
+///     base_strategy.home_dir(),
+///     Path::new("/")
+/// );
+/// assert_eq!(
+///     base_strategy.config_dir(),
+///     Path::new("/.config/")
+/// );
+/// assert_eq!(
+///     base_strategy.data_dir(),
+///     Path::new("/.local/share/")
+/// );
+/// assert_eq!(
+///     base_strategy.cache_dir(),
+///     Path::new("/.cache/")
+/// );
+/// assert_eq!(
+///     base_strategy.state_dir().unwrap(),
+///     Path::new("/.local/state/")
+/// );
+/// assert_eq!(
+///     base_strategy.runtime_dir(),
+///     None
+/// );
+/// assert_eq!(
+///     base_strategy.log_dir().unwrap(),
+///     Path::new("/.local/state/logs/")
+/// );
+/// assert_eq!(
+///     base_strategy.bin_dir(),
+///     Path::new("/.local/bin/")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InMemory {
+    root_dir: PathBuf,
+}
+
+impl InMemory {
+    /// Create a new InMemory BaseStrategy rooted at `/`
+    pub fn new() -> Self {
+        Self {
+            root_dir: PathBuf::from("/"),
+        }
+    }
+
+    /// Create a new InMemory BaseStrategy rooted at the given directory.
+    /// This returns an error if `root_dir` isn’t absolute.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::InMemory;
+    /// use std::path::Path;
+    ///
+    /// let root_path = if cfg!(windows) {
+    ///     "C:\\virtual\\"
+    /// } else {
+    ///     "/virtual/"
+    /// };
+    ///
+    /// let base_strategy = InMemory::with_root_dir(root_path.into()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     base_strategy.config_dir(),
+    ///     Path::new(root_path).join(".config/")
+    /// );
+    /// assert!(InMemory::with_root_dir("virtual".into()).is_err());
+    /// ```
+    pub fn with_root_dir(root_dir: PathBuf) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            root_dir: crate::validate_home_dir(root_dir)?,
+        })
+    }
+}
+
+impl Default for InMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl super::BaseStrategy for InMemory {
+    fn home_dir(&self) -> &Path {
+        &self.root_dir
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.root_dir.join(".config/")
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.root_dir.join(".local/share/")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.root_dir.join(".cache/")
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        Some(self.root_dir.join(".local/state/"))
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    // There is no fallback, as this strategy must never touch the OS.
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "the InMemory strategy has no runtime directory",
+        ))
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        Some(self.root_dir.join(".local/state/logs/"))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.root_dir.join(".local/bin/")
+    }
+}
@@ -0,0 +1,136 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// This strategy keeps everything in a single folder, next to the application’s executable by default. It is meant for self-contained deployments, e.g. applications run from a USB stick, which must not write to the user’s profile.
+///
+/// As there is no user profile involved, [`home_dir`](trait.BaseStrategy.html#tymethod.home_dir) returns the root folder itself, and [`bin_dir`](trait.BaseStrategy.html#tymethod.bin_dir) is the root folder too, as that is where the executable lives.
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::Portable;
+/// use std::path::Path;
+///
+/// let base_strategy = Portable::new().unwrap();
+///
+/// let exe_dir = etcetera::exe_dir().unwrap();
+///
+/// assert_eq!(
+///     base_strategy.home_dir(),
+///     &exe_dir
+/// );
+/// assert_eq!(
+///     base_strategy.config_dir().strip_prefix(&exe_dir),
+///     Ok(Path::new("config/"))
+/// );
+/// assert_eq!(
+///     base_strategy.data_dir().strip_prefix(&exe_dir),
+///     Ok(Path::new("data/"))
+/// );
+/// assert_eq!(
+///     base_strategy.cache_dir().strip_prefix(&exe_dir),
+///     Ok(Path::new("cache/"))
+/// );
+/// assert_eq!(
+///     base_strategy.state_dir().unwrap().strip_prefix(&exe_dir),
+///     Ok(Path::new("state/"))
+/// );
+/// assert_eq!(
+///     base_strategy.runtime_dir().unwrap().strip_prefix(&exe_dir),
+///     Ok(Path::new("runtime/"))
+/// );
+/// assert_eq!(
+///     base_strategy.log_dir().unwrap().strip_prefix(&exe_dir),
+///     Ok(Path::new("logs/"))
+/// );
+/// assert_eq!(
+///     base_strategy.bin_dir(),
+///     exe_dir
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Portable {
+    root_dir: PathBuf,
+}
+
+impl Portable {
+    /// Create a new Portable BaseStrategy rooted at the directory containing the current executable, as given by [`exe_dir`](../fn.exe_dir.html).
+    /// This returns an error if the path of the current executable cannot be determined.
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {
+            root_dir: crate::exe_dir()?,
+        })
+    }
+
+    /// Create a new Portable BaseStrategy rooted at the given directory.
+    /// This returns an error if `root_dir` isn’t absolute.
+    pub fn with_root_dir(root_dir: PathBuf) -> io::Result<Self> {
+        if !root_dir.is_absolute() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the root directory of a portable strategy must be absolute",
+            ));
+        }
+
+        Ok(Self { root_dir })
+    }
+
+    /// Create a new Portable BaseStrategy if the environment variable `env_var` is set to a non-empty value other than `0`, or return `None` otherwise.
+    /// This lets your users opt into portable mode, e.g. with `MYAPP_PORTABLE=1`.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::{choose_base_strategy, BaseStrategy, Portable};
+    ///
+    /// fn strategy() -> Result<Box<dyn BaseStrategy>, Box<dyn std::error::Error>> {
+    ///     Ok(match Portable::from_env("FROBNICATOR_PORTABLE") {
+    ///         Some(portable) => Box::new(portable?),
+    ///         None => Box::new(choose_base_strategy()?),
+    ///     })
+    /// }
+    ///
+    /// std::env::set_var("FROBNICATOR_PORTABLE", "1");
+    /// assert_eq!(strategy().unwrap().home_dir(), Portable::new().unwrap().home_dir());
+    ///
+    /// std::env::set_var("FROBNICATOR_PORTABLE", "0");
+    /// assert_eq!(strategy().unwrap().home_dir(), etcetera::home_dir().unwrap());
+    /// ```
+    pub fn from_env(env_var: &str) -> Option<io::Result<Self>> {
+        match std::env::var_os(env_var) {
+            Some(value) if !value.is_empty() && value != "0" => Some(Self::new()),
+            _ => None,
+        }
+    }
+}
+
+impl super::BaseStrategy for Portable {
+    fn home_dir(&self) -> &Path {
+        &self.root_dir
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.root_dir.join("config/")
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.root_dir.join("data/")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.root_dir.join("cache/")
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        Some(self.root_dir.join("state/"))
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        Some(self.root_dir.join("runtime/"))
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        Some(self.root_dir.join("logs/"))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.root_dir.clone()
+    }
+}
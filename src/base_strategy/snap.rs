@@ -0,0 +1,220 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::env::{EnvProvider, StdEnv};
+use crate::{DirSource, HomeDirError};
+
+use super::{BaseStrategy, Xdg};
+
+/// This strategy is meant for applications distributed as [Snaps](https://snapcraft.io/docs/environment-variables). Inside the confinement, Snap points `HOME` at `$SNAP_USER_DATA`, a folder which is versioned with each revision of the Snap, and provides `$SNAP_USER_COMMON`, which is shared between revisions. Outside of a Snap, this strategy behaves just like [`Xdg`](struct.Xdg.html).
+///
+/// When confined, the configuration, data, state & binary directories are the XDG defaults inside `$SNAP_USER_DATA`, so that Snap can snapshot them & roll them back along with the application. The cache directory is `$SNAP_USER_COMMON/.cache/`, as there is no point in copying caches on every refresh. The `XDG_*_HOME` variables are ignored, as they point outside of the confinement, where the application can’t write. The runtime directory is still read from `XDG_RUNTIME_DIR`, which Snap sets to a folder of its own.
+///
+/// The system-wide `$SNAP_DATA` folder, which is only writable by daemons running as root, is available through [`snap_data_dir`](#method.snap_data_dir).
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::Snap;
+/// use std::collections::HashMap;
+/// use std::path::Path;
+///
+/// let home_dir = etcetera::home_dir().unwrap();
+/// let user_data = home_dir.join("snap/frobnicator/42");
+/// let user_common = home_dir.join("snap/frobnicator/common");
+///
+/// let base_strategy = Snap::new_with_env(HashMap::from([
+///     ("SNAP", Path::new("/snap/frobnicator/42").as_os_str()),
+///     ("SNAP_NAME", "frobnicator".as_ref()),
+///     ("SNAP_USER_DATA", user_data.as_os_str()),
+///     ("SNAP_USER_COMMON", user_common.as_os_str()),
+/// ])).unwrap();
+///
+/// assert!(base_strategy.is_confined());
+/// assert_eq!(base_strategy.snap_name().as_deref(), Some("frobnicator"));
+/// assert_eq!(base_strategy.config_dir(), user_data.join(".config/"));
+/// assert_eq!(base_strategy.data_dir(), user_data.join(".local/share/"));
+/// assert_eq!(base_strategy.cache_dir(), user_common.join(".cache/"));
+/// assert_eq!(base_strategy.state_dir(), Some(user_data.join(".local/state/")));
+/// assert_eq!(base_strategy.bin_dir(), user_data.join(".local/bin/"));
+///
+/// // Without `SNAP`, this is the same as the `Xdg` strategy.
+/// let base_strategy = Snap::new_with_env(HashMap::<&str, &str>::new()).unwrap();
+/// assert!(!base_strategy.is_confined());
+/// assert_eq!(
+///     base_strategy.config_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".config/"))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snap<E = StdEnv> {
+    xdg: Xdg<E>,
+    confinement: Option<Confinement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Confinement {
+    user_data: PathBuf,
+    user_common: PathBuf,
+}
+
+impl Snap {
+    /// Create a new Snap BaseStrategy
+    pub fn new() -> Result<Self, HomeDirError> {
+        Self::new_with_env(StdEnv)
+    }
+}
+
+impl<E: EnvProvider> Snap<E> {
+    /// Create a new Snap BaseStrategy which reads the environment variables from `env` instead of the process’ environment.
+    pub fn new_with_env(env: E) -> Result<Self, HomeDirError> {
+        let xdg = Xdg::new_with_env(env)?;
+        // Snap always sets `SNAP` inside the confinement.
+        let confinement = match xdg.env().var_os("SNAP") {
+            Some(_) => absolute_var(xdg.env(), "SNAP_USER_DATA")
+                .zip(absolute_var(xdg.env(), "SNAP_USER_COMMON"))
+                .map(|(user_data, user_common)| Confinement {
+                    user_data,
+                    user_common,
+                }),
+            None => None,
+        };
+
+        Ok(Self { xdg, confinement })
+    }
+
+    /// Returns whether the application is running inside a Snap.
+    pub fn is_confined(&self) -> bool {
+        self.confinement.is_some()
+    }
+
+    /// Gets the name of the Snap from `SNAP_NAME`, if it is set.
+    pub fn snap_name(&self) -> Option<String> {
+        self.xdg
+            .env()
+            .var_os("SNAP_NAME")
+            .and_then(|name| name.into_string().ok())
+    }
+
+    /// Gets the system-wide, versioned data folder of the Snap from `SNAP_DATA`, if it is set.
+    /// This is only writable by daemons running as root.
+    pub fn snap_data_dir(&self) -> Option<PathBuf> {
+        absolute_var(self.xdg.env(), "SNAP_DATA")
+    }
+
+    fn user_data_dir(&self, default: &str) -> Option<(PathBuf, DirSource)> {
+        let confinement = self.confinement.as_ref()?;
+        let path = confinement.user_data.join(default);
+        Some((path, DirSource::EnvVar("SNAP_USER_DATA")))
+    }
+}
+
+fn absolute_var<E: EnvProvider>(env: &E, key: &str) -> Option<PathBuf> {
+    env.var_os(key)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+impl<E: EnvProvider> BaseStrategy for Snap<E> {
+    fn home_dir(&self) -> &Path {
+        self.xdg.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.xdg.env_var_os(key)
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.config_dir_with_source().0
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.data_dir_with_source().0
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.cache_dir_with_source().0
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.state_dir_with_source().map(|(path, _)| path)
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.xdg.runtime_dir()
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        match self.user_data_dir(".local/state/logs") {
+            Some((path, _)) => Some(path),
+            None => self.xdg.log_dir(),
+        }
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        match self.user_data_dir(".local/bin/") {
+            Some((path, _)) => path,
+            None => self.xdg.bin_dir(),
+        }
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.user_data_dir(".config/")
+            .unwrap_or_else(|| self.xdg.config_dir_with_source())
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.user_data_dir(".local/share/")
+            .unwrap_or_else(|| self.xdg.data_dir_with_source())
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        match &self.confinement {
+            Some(confinement) => (
+                confinement.user_common.join(".cache/"),
+                DirSource::EnvVar("SNAP_USER_COMMON"),
+            ),
+            None => self.xdg.cache_dir_with_source(),
+        }
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.user_data_dir(".local/state/")
+            .or_else(|| self.xdg.state_dir_with_source())
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.xdg.runtime_dir_with_source()
+    }
+
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        self.xdg.desktop_dir()
+    }
+
+    fn documents_dir(&self) -> Option<PathBuf> {
+        self.xdg.documents_dir()
+    }
+
+    fn downloads_dir(&self) -> Option<PathBuf> {
+        self.xdg.downloads_dir()
+    }
+
+    fn music_dir(&self) -> Option<PathBuf> {
+        self.xdg.music_dir()
+    }
+
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        self.xdg.pictures_dir()
+    }
+
+    fn public_dir(&self) -> Option<PathBuf> {
+        self.xdg.public_dir()
+    }
+
+    fn templates_dir(&self) -> Option<PathBuf> {
+        self.xdg.templates_dir()
+    }
+
+    fn videos_dir(&self) -> Option<PathBuf> {
+        self.xdg.videos_dir()
+    }
+}
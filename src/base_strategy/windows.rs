@@ -1,9 +1,15 @@
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 
-use crate::HomeDirError;
+use crate::env::{EnvProvider, StdEnv};
+use crate::{DirSource, HomeDirError};
 
 /// This strategy follows Windows’ conventions. It seems that all Windows GUI apps, and some command-line ones follow this pattern. The specification is available [here](https://docs.microsoft.com/en-us/windows/win32/shell/knownfolderid).
 ///
+/// The directories are looked up through the `SHGetKnownFolderPath` API first, as the environment variables can be missing or stale, e.g. for services or impersonated users. The `APPDATA` & `LOCALAPPDATA` variables are only used if the API fails, and the default locations inside the home directory after that. The home directory itself comes from [`home_dir`](../fn.home_dir.html), which prefers an absolute `HOME` over `USERPROFILE`.
+///
+/// Windows has no dedicated state or runtime folders, so the state directory is the local app data folder and the runtime directory is its `Temp` subfolder, the same one `%TEMP%` points at by default.
+///
 /// This initial example removes all the relevant environment variables to show the strategy’s use of the:
 /// - (on Windows) SHGetKnownFolderPath API.
 /// - (on non-Windows) Windows default directories.
@@ -39,16 +45,36 @@ use crate::HomeDirError;
 ///     Ok(Path::new("AppData/Local/"))
 /// );
 /// assert_eq!(
-///     base_strategy.state_dir(),
-///     None
+///     base_strategy.local_data_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/"))
+/// );
+/// assert_eq!(
+///     base_strategy.state_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/"))
 /// );
 /// assert_eq!(
-///     base_strategy.runtime_dir(),
-///     None
+///     base_strategy.runtime_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Temp/"))
+/// );
+/// assert_eq!(
+///     base_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Logs/"))
+/// );
+/// assert_eq!(
+///     base_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Programs/"))
+/// );
+/// assert_eq!(
+///     base_strategy.documents_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Documents/"))
+/// );
+/// assert_eq!(
+///     base_strategy.downloads_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new("Downloads/"))
 /// );
 /// ```
 ///
-/// This next example gives the environment variables values:
+/// The environment variables are only used if `SHGetKnownFolderPath` fails, so this next example, which gives them values, only applies outside of Windows:
 ///
 /// ```
 /// use etcetera::base_strategy::BaseStrategy;
@@ -75,6 +101,10 @@ use crate::HomeDirError;
 /// std::env::set_var("APPDATA", data_path);
 /// std::env::set_var("LOCALAPPDATA", cache_path);
 ///
+/// if cfg!(windows) {
+///     return;
+/// }
+///
 /// let base_strategy = Windows::new().unwrap();
 ///
 /// assert_eq!(
@@ -94,18 +124,43 @@ use crate::HomeDirError;
 ///     Path::new(cache_path)
 /// );
 /// assert_eq!(
-///     base_strategy.state_dir(),
-///     None
+///     base_strategy.state_dir().unwrap(),
+///     Path::new(cache_path)
+/// );
+/// assert_eq!(
+///     base_strategy.runtime_dir().unwrap(),
+///     Path::new(cache_path).join("Temp")
 /// );
 /// assert_eq!(
-///     base_strategy.runtime_dir(),
-///     None
+///     base_strategy.log_dir().unwrap(),
+///     Path::new(cache_path).join("Logs")
+/// );
+/// assert_eq!(
+///     base_strategy.bin_dir(),
+///     Path::new(cache_path).join("Programs")
 /// );
 /// ```
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Windows {
+pub struct Windows<E = StdEnv> {
     home_dir: PathBuf,
+    env: E,
+}
+
+// The Known Folders we look up through `SHGetKnownFolderPath`.
+#[derive(Debug, Clone, Copy)]
+enum KnownFolder {
+    RoamingAppData,
+    LocalAppData,
+    Desktop,
+    Documents,
+    Downloads,
+    Music,
+    Pictures,
+    Public,
+    Templates,
+    Videos,
+    ProgramData,
 }
 
 impl Windows {
@@ -113,20 +168,73 @@ impl Windows {
     pub fn new() -> Result<Self, HomeDirError> {
         Ok(Self {
             home_dir: crate::home_dir()?,
+            env: StdEnv,
+        })
+    }
+
+    /// Create a new Windows BaseStrategy using the given home directory instead of the current user’s one.
+    /// This returns an error if `home_dir` isn’t absolute.
+    pub fn with_home_dir(home_dir: PathBuf) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            home_dir: crate::validate_home_dir(home_dir)?,
+            env: StdEnv,
+        })
+    }
+}
+
+impl<E: EnvProvider> Windows<E> {
+    /// Create a new Windows BaseStrategy which reads `APPDATA` & `LOCALAPPDATA` from `env` instead of the process’ environment.
+    /// These are only used if `SHGetKnownFolderPath` fails.
+    pub fn new_with_env(env: E) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            home_dir: crate::home_dir()?,
+            env,
         })
     }
 
-    fn dir_inner(env: &'static str) -> Option<PathBuf> {
-        std::env::var_os(env)
+    /// Replaces the environment `APPDATA` & `LOCALAPPDATA` are read from.
+    pub fn with_env<F: EnvProvider>(self, env: F) -> Windows<F> {
+        Windows {
+            home_dir: self.home_dir,
+            env,
+        }
+    }
+
+    /// Gets the folder shared by all users for application data, i.e. the `ProgramData` Known Folder (usually `C:\\ProgramData`) or the `ProgramData` environment variable.
+    /// This is `None` if neither is available, which is always the case outside of Windows unless `ProgramData` is set.
+    pub fn program_data_dir(&self) -> Option<PathBuf> {
+        self.dir_inner("ProgramData", KnownFolder::ProgramData)
+    }
+
+    fn dir_inner(&self, env: &'static str, folder: KnownFolder) -> Option<PathBuf> {
+        self.dir_inner_with_source(env, folder)
+            .map(|(path, _)| path)
+    }
+
+    // The Known Folder is authoritative, the environment variable is only a fallback.
+    fn dir_inner_with_source(
+        &self,
+        env: &'static str,
+        folder: KnownFolder,
+    ) -> Option<(PathBuf, DirSource)> {
+        if let Some(path) = Self::dir_crt(folder) {
+            return Some((path, DirSource::Default));
+        }
+
+        self.env
+            .var_os(env)
             .filter(|s| !s.is_empty())
-            .map(PathBuf::from)
-            .or_else(|| Self::dir_crt(env))
+            .map(|path| (PathBuf::from(path), DirSource::EnvVar(env)))
+    }
+
+    fn user_dir(&self, folder: KnownFolder, default: &str) -> PathBuf {
+        Self::dir_crt(folder).unwrap_or_else(|| self.home_dir.join(default))
     }
 
     // Ref: https://github.com/rust-lang/cargo/blob/home-0.5.11/crates/home/src/windows.rs
     // We should keep this code in sync with the above.
     #[cfg(all(windows, not(target_vendor = "uwp")))]
-    fn dir_crt(env: &'static str) -> Option<PathBuf> {
+    fn dir_crt(folder: KnownFolder) -> Option<PathBuf> {
         use std::ffi::OsString;
         use std::os::windows::ffi::OsStringExt;
         use std::ptr;
@@ -135,7 +243,9 @@ impl Windows {
         use windows_sys::Win32::Foundation::S_OK;
         use windows_sys::Win32::System::Com::CoTaskMemFree;
         use windows_sys::Win32::UI::Shell::{
-            FOLDERID_LocalAppData, FOLDERID_RoamingAppData, SHGetKnownFolderPath,
+            FOLDERID_Desktop, FOLDERID_Documents, FOLDERID_Downloads, FOLDERID_LocalAppData,
+            FOLDERID_Music, FOLDERID_Pictures, FOLDERID_ProgramData, FOLDERID_Public,
+            FOLDERID_RoamingAppData, FOLDERID_Templates, FOLDERID_Videos, SHGetKnownFolderPath,
             KF_FLAG_DONT_VERIFY,
         };
 
@@ -143,10 +253,18 @@ impl Windows {
             fn wcslen(buf: *const u16) -> usize;
         }
 
-        let folder_id = match env {
-            "APPDATA" => FOLDERID_RoamingAppData,
-            "LOCALAPPDATA" => FOLDERID_LocalAppData,
-            _ => return None,
+        let folder_id = match folder {
+            KnownFolder::RoamingAppData => FOLDERID_RoamingAppData,
+            KnownFolder::LocalAppData => FOLDERID_LocalAppData,
+            KnownFolder::Desktop => FOLDERID_Desktop,
+            KnownFolder::Documents => FOLDERID_Documents,
+            KnownFolder::Downloads => FOLDERID_Downloads,
+            KnownFolder::Music => FOLDERID_Music,
+            KnownFolder::Pictures => FOLDERID_Pictures,
+            KnownFolder::Public => FOLDERID_Public,
+            KnownFolder::Templates => FOLDERID_Templates,
+            KnownFolder::Videos => FOLDERID_Videos,
+            KnownFolder::ProgramData => FOLDERID_ProgramData,
         };
 
         unsafe {
@@ -173,34 +291,126 @@ impl Windows {
     }
 
     #[cfg(not(all(windows, not(target_vendor = "uwp"))))]
-    fn dir_crt(_env: &'static str) -> Option<PathBuf> {
+    fn dir_crt(_folder: KnownFolder) -> Option<PathBuf> {
         None
     }
 }
 
-impl super::BaseStrategy for Windows {
+// The XDG strategy searches the same folder when `XDG_CONFIG_DIRS` or `XDG_DATA_DIRS` is unset. It has no use for the home directory.
+#[cfg(all(windows, feature = "xdg"))]
+pub(super) fn program_data_dir<E: EnvProvider>(env: E) -> Option<PathBuf> {
+    Windows {
+        home_dir: PathBuf::new(),
+        env,
+    }
+    .program_data_dir()
+}
+
+impl<E: EnvProvider> super::BaseStrategy for Windows<E> {
     fn home_dir(&self) -> &Path {
         &self.home_dir
     }
 
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.env.var_os(key)
+    }
+
     fn config_dir(&self) -> PathBuf {
         self.data_dir()
     }
 
     fn data_dir(&self) -> PathBuf {
-        Self::dir_inner("APPDATA").unwrap_or_else(|| self.home_dir.join("AppData").join("Roaming"))
+        self.data_dir_with_source().0
+    }
+
+    fn local_data_dir(&self) -> PathBuf {
+        self.cache_dir()
     }
 
     fn cache_dir(&self) -> PathBuf {
-        Self::dir_inner("LOCALAPPDATA")
-            .unwrap_or_else(|| self.home_dir.join("AppData").join("Local"))
+        self.cache_dir_with_source().0
     }
 
     fn state_dir(&self) -> Option<PathBuf> {
-        None
+        Some(self.cache_dir())
     }
 
     fn runtime_dir(&self) -> Option<PathBuf> {
-        None
+        Some(self.cache_dir().join("Temp"))
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        Some(self.cache_dir().join("Logs"))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.cache_dir().join("Programs")
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.data_dir_with_source()
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.dir_inner_with_source("APPDATA", KnownFolder::RoamingAppData)
+            .unwrap_or_else(|| {
+                let path = self.home_dir.join("AppData").join("Roaming");
+                (path, DirSource::Fallback)
+            })
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.dir_inner_with_source("LOCALAPPDATA", KnownFolder::LocalAppData)
+            .unwrap_or_else(|| {
+                let path = self.home_dir.join("AppData").join("Local");
+                (path, DirSource::Fallback)
+            })
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        Some(self.cache_dir_with_source())
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        let (cache_dir, source) = self.cache_dir_with_source();
+        Some((cache_dir.join("Temp"), source))
+    }
+
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        Some(self.user_dir(KnownFolder::Desktop, "Desktop"))
+    }
+
+    fn documents_dir(&self) -> Option<PathBuf> {
+        Some(self.user_dir(KnownFolder::Documents, "Documents"))
+    }
+
+    fn downloads_dir(&self) -> Option<PathBuf> {
+        Some(self.user_dir(KnownFolder::Downloads, "Downloads"))
+    }
+
+    fn music_dir(&self) -> Option<PathBuf> {
+        Some(self.user_dir(KnownFolder::Music, "Music"))
+    }
+
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        Some(self.user_dir(KnownFolder::Pictures, "Pictures"))
+    }
+
+    // The public folder is shared by all users, so there is no sensible default inside the home directory.
+    fn public_dir(&self) -> Option<PathBuf> {
+        Self::dir_crt(KnownFolder::Public)
+    }
+
+    fn templates_dir(&self) -> Option<PathBuf> {
+        Some(Self::dir_crt(KnownFolder::Templates).unwrap_or_else(|| {
+            self.data_dir()
+                .join("Microsoft")
+                .join("Windows")
+                .join("Templates")
+        }))
+    }
+
+    fn videos_dir(&self) -> Option<PathBuf> {
+        Some(self.user_dir(KnownFolder::Videos, "Videos"))
     }
 }
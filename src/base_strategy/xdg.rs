@@ -1,7 +1,13 @@
+use std::ffi::OsString;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
-use crate::HomeDirError;
+use crate::env::{DotEnv, EnvProvider, NoEnv, StdEnv};
+use crate::spec::{BaseDir, SearchDirs};
+use crate::{DirSource, HomeDirError};
+
+mod user_dirs;
 
 /// This strategy implements the [XDG Base Directories Specification](https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html). It is the most common on Linux, but is increasingly being adopted elsewhere.
 ///
@@ -18,6 +24,7 @@ use crate::HomeDirError;
 /// std::env::remove_var("XDG_CACHE_HOME");
 /// std::env::remove_var("XDG_STATE_HOME");
 /// std::env::remove_var("XDG_RUNTIME_DIR");
+/// std::env::remove_var("XDG_BIN_HOME");
 ///
 /// let base_strategy = Xdg::new().unwrap();
 ///
@@ -47,6 +54,14 @@ use crate::HomeDirError;
 ///     base_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     base_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/state/logs/"))
+/// );
+/// assert_eq!(
+///     base_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/bin/"))
+/// );
 /// ```
 ///
 /// This next example gives the environment variables values:
@@ -111,6 +126,10 @@ use crate::HomeDirError;
 ///     base_strategy.runtime_dir().unwrap(),
 ///     Path::new(runtime_path)
 /// );
+/// assert_eq!(
+///     base_strategy.log_dir().unwrap(),
+///     Path::new(state_path).join("logs")
+/// );
 /// ```
 ///
 /// The XDG spec requires that when the environment variables’ values are not absolute paths, their values should be ignored. This example exemplifies this behaviour:
@@ -126,6 +145,7 @@ use crate::HomeDirError;
 /// std::env::set_var("XDG_CACHE_HOME", "baz/");
 /// std::env::set_var("XDG_STATE_HOME", "foobar/");
 /// std::env::set_var("XDG_RUNTIME_DIR", "qux/");
+/// std::env::set_var("XDG_BIN_HOME", "quux/");
 ///
 /// let base_strategy = Xdg::new().unwrap();
 ///
@@ -152,10 +172,79 @@ use crate::HomeDirError;
 ///     base_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     base_strategy.log_dir().unwrap().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/state/logs/"))
+/// );
+/// assert_eq!(
+///     base_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/bin/"))
+/// );
+/// ```
+///
+/// The user’s folders, such as `Documents` or `Downloads`, are read from the `XDG_*_DIR` environment variables, or from the `user-dirs.dirs` file in the configuration directory.
+/// Folders that are missing from the file (or if there is no file) default to the usual names inside the home directory, and folders that the file points at the home directory itself are considered disabled:
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::Xdg;
+/// use std::collections::HashMap;
+///
+/// let home_dir = etcetera::home_dir().unwrap();
+/// let config_dir = std::env::temp_dir().join(format!("etcetera-user-dirs-{}", std::process::id()));
+/// std::fs::create_dir_all(&config_dir).unwrap();
+/// std::fs::write(
+///     config_dir.join("user-dirs.dirs"),
+///     [
+///         "# This file is written by xdg-user-dirs-update",
+///         "XDG_DESKTOP_DIR=\"$HOME/\"",
+///         "XDG_DOWNLOAD_DIR=\"$HOME/Incoming\"\r",
+///         "XDG_DOCUMENTS_DIR=\"$HOME/My \\\"Documents\\\"\"",
+///         "XDG_PICTURES_DIR=\"/srv/pictures\"",
+///         "XDG_VIDEOS_DIR=\"relative/videos\"",
+///         "XDG_MUSIC_DIR=\"$HOME/Music\"",
+///         "XDG_MUSIC_DIR=\"$HOME/Tunes\"",
+///     ]
+///     .join("\n"),
+/// )
+/// .unwrap();
+///
+/// let base_strategy = Xdg::new_with_env(HashMap::from([
+///     ("XDG_CONFIG_HOME", config_dir.clone()),
+///     ("XDG_TEMPLATES_DIR", home_dir.join("Skeletons")),
+/// ])).unwrap();
+///
+/// // Disabled.
+/// assert_eq!(base_strategy.desktop_dir(), None);
+/// // CRLF line endings are fine.
+/// assert_eq!(base_strategy.downloads_dir(), Some(home_dir.join("Incoming")));
+/// // Escaped characters.
+/// assert_eq!(base_strategy.documents_dir(), Some(home_dir.join("My \"Documents\"")));
+/// // Absolute paths are used as-is.
+/// if cfg!(unix) {
+///     assert_eq!(base_strategy.pictures_dir(), Some("/srv/pictures".into()));
+/// }
+/// // Relative paths are ignored, so we get the default.
+/// assert_eq!(base_strategy.videos_dir(), Some(home_dir.join("Videos")));
+/// // The last assignment wins.
+/// assert_eq!(base_strategy.music_dir(), Some(home_dir.join("Tunes")));
+/// // Missing keys also get the default.
+/// assert_eq!(base_strategy.public_dir(), Some(home_dir.join("Public")));
+/// // The environment variables take precedence.
+/// assert_eq!(base_strategy.templates_dir(), Some(home_dir.join("Skeletons")));
+///
+/// // Without a file, every folder gets the default.
+/// std::fs::remove_dir_all(&config_dir).unwrap();
+/// assert_eq!(base_strategy.desktop_dir(), Some(home_dir.join("Desktop")));
+/// assert_eq!(base_strategy.downloads_dir(), Some(home_dir.join("Downloads")));
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Xdg {
+pub struct Xdg<E = StdEnv> {
     home_dir: PathBuf,
+    env: E,
+    expand_tilde: bool,
+    expand_vars: bool,
+    validate_runtime_dir: bool,
 }
 
 impl Xdg {
@@ -163,12 +252,282 @@ impl Xdg {
     pub fn new() -> Result<Self, HomeDirError> {
         Ok(Self {
             home_dir: crate::home_dir()?,
+            env: StdEnv,
+            expand_tilde: false,
+            expand_vars: false,
+            validate_runtime_dir: false,
+        })
+    }
+
+    /// Create a new Xdg BaseStrategy using the given home directory instead of the current user’s one.
+    /// This returns an error if `home_dir` isn’t absolute.
+    ///
+    /// Environment variables such as `XDG_CONFIG_HOME` still take precedence over the directories derived from `home_dir`.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::collections::HashMap;
+    /// use std::path::Path;
+    ///
+    /// let home_path = if cfg!(windows) {
+    ///     "C:\\my_home_location\\"
+    /// } else {
+    ///     "/my_home_location/"
+    /// };
+    ///
+    /// // Use an empty environment so that the test doesn’t depend on the process’ XDG variables.
+    /// let base_strategy = Xdg::with_home_dir(home_path.into())
+    ///     .unwrap()
+    ///     .with_env(HashMap::<String, String>::new());
+    ///
+    /// assert_eq!(base_strategy.home_dir(), Path::new(home_path));
+    /// assert_eq!(base_strategy.config_dir(), Path::new(home_path).join(".config/"));
+    ///
+    /// // Relative home directories are rejected.
+    /// assert!(Xdg::with_home_dir("my_home_location".into()).is_err());
+    /// ```
+    pub fn with_home_dir(home_dir: PathBuf) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            home_dir: crate::validate_home_dir(home_dir)?,
+            env: StdEnv,
+            expand_tilde: false,
+            expand_vars: false,
+            validate_runtime_dir: false,
+        })
+    }
+}
+
+impl Xdg<NoEnv> {
+    /// Create a new Xdg BaseStrategy for the user with the ID `uid` rather than the current one, e.g. for a system daemon managing per-user services. Their home directory is looked up in the password database.
+    /// This returns an error if there is no such user, or if they have no home directory.
+    ///
+    /// The environment variables of the current process describe the current user, so they are skipped: only the default directories are used, e.g. `~/.config/` for [`config_dir`](trait.BaseStrategy.html#tymethod.config_dir), and there is no runtime directory. Use [`with_env`](#method.with_env) if you know the other user’s variables.
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    ///
+    /// let base_strategy = Xdg::for_user(etcetera::effective_uid()).unwrap();
+    ///
+    /// assert!(base_strategy.home_dir().is_absolute());
+    /// assert_eq!(base_strategy.config_dir(), base_strategy.home_dir().join(".config/"));
+    /// assert_eq!(base_strategy.runtime_dir(), None);
+    ///
+    /// let err = Xdg::for_user(4_000_000_000).unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "could not locate home directory of the user with ID 4000000000"
+    /// );
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn for_user(uid: u32) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            home_dir: crate::validate_home_dir(crate::user_home_dir(uid)?)?,
+            env: NoEnv,
+            expand_tilde: false,
+            expand_vars: false,
+            validate_runtime_dir: false,
+        })
+    }
+}
+
+impl Xdg<DotEnv> {
+    /// Create a new Xdg BaseStrategy which reads the XDG variables from the `.env`-style file at `path` first, and from the process’ environment otherwise. See [`DotEnv`](../env/struct.DotEnv.html) for the format of the file.
+    ///
+    /// A missing file is treated like an empty one. If the home directory can’t be located, the returned error has the kind `NotFound` and wraps a [`HomeDirError`](../struct.HomeDirError.html).
+    pub fn from_dotenv<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let env = DotEnv::load(path)?;
+        Self::new_with_env(env).map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))
+    }
+}
+
+#[cfg(all(feature = "proc-environ", target_os = "linux"))]
+impl Xdg<crate::env::ProcEnv> {
+    /// Create a new Xdg BaseStrategy which resolves the directories the process with the ID `pid` would use, by reading the environment it was started with from `/proc/<pid>/environ`. See [`ProcEnv`](../env/struct.ProcEnv.html) for the details. This is only available on Linux with the `proc-environ` feature.
+    ///
+    /// The home directory is the process’ `HOME` if that is absolute, and otherwise the home directory of the user owning the process. This returns an error if the file cannot be read, e.g. because the process doesn’t exist or belongs to another user, or if the home directory cannot be located, in which case the error has the kind `NotFound` and wraps a [`HomeDirError`](../struct.HomeDirError.html).
+    ///
+    /// ```
+    /// # #[cfg(target_os = "linux")]
+    /// # {
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    ///
+    /// // The environment of this process hasn’t changed since it started.
+    /// let base_strategy = Xdg::from_proc_environ(std::process::id()).unwrap();
+    /// assert_eq!(base_strategy.config_dir(), Xdg::new().unwrap().config_dir());
+    ///
+    /// assert!(Xdg::from_proc_environ(u32::MAX).is_err());
+    /// # }
+    /// ```
+    pub fn from_proc_environ(pid: u32) -> io::Result<Self> {
+        use std::os::unix::fs::MetadataExt;
+
+        let env = crate::env::ProcEnv::load(pid)?;
+        let home_dir = match env.var_os("HOME").map(PathBuf::from) {
+            Some(home_dir) if home_dir.is_absolute() => home_dir,
+            _ => {
+                let uid = std::fs::metadata(format!("/proc/{pid}"))?.uid();
+                crate::user_home_dir(uid)
+                    .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?
+            }
+        };
+
+        Ok(Self {
+            home_dir,
+            env,
+            expand_tilde: false,
+            expand_vars: false,
+            validate_runtime_dir: false,
+        })
+    }
+}
+
+impl<E: EnvProvider> Xdg<E> {
+    /// Create a new Xdg BaseStrategy which reads the XDG environment variables from `env` instead of the process’ environment.
+    /// See the [`env`](../env/index.html) module for an example.
+    pub fn new_with_env(env: E) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            home_dir: crate::home_dir()?,
+            env,
+            expand_tilde: false,
+            expand_vars: false,
+            validate_runtime_dir: false,
         })
     }
 
-    fn env_var_or_none(env_var: &str) -> Option<PathBuf> {
-        std::env::var_os(env_var).and_then(|path| {
-            let path = PathBuf::from(path);
+    /// Replaces the environment the XDG environment variables are read from.
+    pub fn with_env<F: EnvProvider>(self, env: F) -> Xdg<F> {
+        Xdg {
+            home_dir: self.home_dir,
+            env,
+            expand_tilde: self.expand_tilde,
+            expand_vars: self.expand_vars,
+            validate_runtime_dir: self.validate_runtime_dir,
+        }
+    }
+
+    /// Sets whether values of the XDG environment variables which start with `~` are expanded against the home directory.
+    /// This is disabled by default, as the XDG spec requires these values to be absolute, but users sometimes export e.g. `XDG_CONFIG_HOME=~/config` by mistake.
+    ///
+    /// Only `~` and `~/…` are expanded; other users’ home directories (`~user/…`) are still ignored.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::collections::HashMap;
+    ///
+    /// let env = HashMap::from([
+    ///     ("XDG_CONFIG_HOME", "~/my_config"),
+    ///     ("XDG_DATA_HOME", "~"),
+    ///     ("XDG_CACHE_HOME", "~user/my_cache"),
+    /// ]);
+    /// let home_dir = etcetera::home_dir().unwrap();
+    ///
+    /// // Without tilde expansion, the values are relative, so they are ignored.
+    /// let base_strategy = Xdg::new_with_env(&env).unwrap();
+    /// assert_eq!(base_strategy.config_dir(), home_dir.join(".config/"));
+    /// assert_eq!(base_strategy.data_dir(), home_dir.join(".local/share/"));
+    ///
+    /// let base_strategy = Xdg::new_with_env(&env).unwrap().with_tilde_expansion(true);
+    /// assert_eq!(base_strategy.config_dir(), home_dir.join("my_config"));
+    /// assert_eq!(base_strategy.data_dir(), home_dir);
+    /// assert_eq!(base_strategy.cache_dir(), home_dir.join(".cache/"));
+    /// ```
+    pub fn with_tilde_expansion(mut self, expand_tilde: bool) -> Self {
+        self.expand_tilde = expand_tilde;
+        self
+    }
+
+    /// Sets whether references to other environment variables in the values of the XDG environment variables, i.e. `$VAR` & `${VAR}`, are expanded, as a shell would.
+    /// This is disabled by default, as the XDG spec requires these values to be absolute paths, but some tools pass on values like `$HOME/.data` without expanding them.
+    ///
+    /// The variables are read from the same environment as the XDG variables, and unknown ones expand to nothing. `HOME` falls back to the strategy’s home directory. A `$` which isn’t followed by a variable name is kept as-is, as are values which aren’t valid UTF-8. The expansion happens before [tilde expansion](#method.with_tilde_expansion) and the check for absolute paths.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::collections::HashMap;
+    ///
+    /// let env = HashMap::from([
+    ///     ("XDG_DATA_HOME", "$HOME/.data"),
+    ///     ("XDG_CONFIG_HOME", "${HOME}/x"),
+    ///     ("XDG_CACHE_HOME", "$FROBNICATOR_ROOT/cache"),
+    /// ]);
+    /// let home_dir = etcetera::home_dir().unwrap();
+    ///
+    /// // Without variable expansion, the values are relative, so they are ignored.
+    /// let base_strategy = Xdg::new_with_env(&env).unwrap();
+    /// assert_eq!(base_strategy.data_dir(), home_dir.join(".local/share/"));
+    ///
+    /// let base_strategy = Xdg::new_with_env(&env).unwrap().with_var_expansion(true);
+    /// assert_eq!(base_strategy.data_dir(), home_dir.join(".data"));
+    /// assert_eq!(base_strategy.config_dir(), home_dir.join("x"));
+    ///
+    /// // `FROBNICATOR_ROOT` is unset, so this expands to `/cache`.
+    /// if cfg!(unix) {
+    ///     assert_eq!(base_strategy.cache_dir(), std::path::Path::new("/cache"));
+    /// }
+    /// ```
+    pub fn with_var_expansion(mut self, expand_vars: bool) -> Self {
+        self.expand_vars = expand_vars;
+        self
+    }
+
+    /// Sets whether `XDG_RUNTIME_DIR` is checked against the requirements of the XDG spec before it is used: on Unix, it must be a directory owned by the current user, with the permissions `0700`. Otherwise, [`runtime_dir`](trait.BaseStrategy.html#tymethod.runtime_dir) is `None`, like when the variable isn’t set.
+    ///
+    /// This is disabled by default, as it touches the filesystem on every call. There is nothing to check on other platforms.
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// use etcetera::base_strategy::{BaseStrategy, Xdg};
+    /// use std::collections::HashMap;
+    /// use std::os::unix::fs::PermissionsExt;
+    ///
+    /// let runtime_dir = std::env::temp_dir().join(format!("etcetera-validate-runtime-dir-{}", std::process::id()));
+    /// std::fs::create_dir_all(&runtime_dir).unwrap();
+    /// std::fs::set_permissions(&runtime_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+    ///
+    /// let base_strategy = Xdg::new_with_env(HashMap::from([("XDG_RUNTIME_DIR", runtime_dir.clone())]))
+    ///     .unwrap()
+    ///     .with_runtime_dir_validation(true);
+    ///
+    /// // Other users can read the directory.
+    /// assert_eq!(base_strategy.runtime_dir(), None);
+    ///
+    /// std::fs::set_permissions(&runtime_dir, std::fs::Permissions::from_mode(0o700)).unwrap();
+    /// assert_eq!(base_strategy.runtime_dir(), Some(runtime_dir.clone()));
+    ///
+    /// std::fs::remove_dir(&runtime_dir).unwrap();
+    /// assert_eq!(base_strategy.runtime_dir(), None);
+    /// # }
+    /// ```
+    pub fn with_runtime_dir_validation(mut self, validate_runtime_dir: bool) -> Self {
+        self.validate_runtime_dir = validate_runtime_dir;
+        self
+    }
+
+    pub(super) fn env(&self) -> &E {
+        &self.env
+    }
+
+    pub(super) fn env_var_or_none(&self, env_var: &str) -> Option<PathBuf> {
+        self.env.var_os(env_var).and_then(|path| {
+            let path = if self.expand_vars {
+                self.expand_vars(path)
+            } else {
+                path
+            };
+            let path = if self.expand_tilde {
+                self.expand_tilde(PathBuf::from(path))
+            } else {
+                PathBuf::from(path)
+            };
 
             // Return None if the path obtained from the environment variable isn’t absolute.
             if path.is_absolute() {
@@ -179,33 +538,335 @@ impl Xdg {
         })
     }
 
-    fn env_var_or_default(&self, env_var: &str, default: impl AsRef<Path>) -> PathBuf {
-        Self::env_var_or_none(env_var).unwrap_or_else(|| self.home_dir.join(default))
+    fn expand_vars(&self, value: OsString) -> OsString {
+        let Some(mut rest) = value.to_str() else {
+            return value;
+        };
+
+        let mut expanded = OsString::new();
+        while let Some(start) = rest.find('$') {
+            expanded.push(&rest[..start]);
+            let after = &rest[start + 1..];
+
+            let (name, remainder) = match after.strip_prefix('{') {
+                Some(braced) => match braced.split_once('}') {
+                    Some((name, remainder)) if is_var_name(name) => (name, remainder),
+                    _ => ("", after),
+                },
+                None => {
+                    let len = after
+                        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                        .unwrap_or(after.len());
+                    if is_var_name(&after[..len]) {
+                        (&after[..len], &after[len..])
+                    } else {
+                        ("", after)
+                    }
+                }
+            };
+
+            if name.is_empty() {
+                expanded.push("$");
+            } else if let Some(value) = self.env.var_os(name) {
+                expanded.push(value);
+            } else if name == "HOME" {
+                expanded.push(&self.home_dir);
+            }
+            rest = remainder;
+        }
+        expanded.push(rest);
+
+        expanded
+    }
+
+    fn expand_tilde(&self, path: PathBuf) -> PathBuf {
+        // `~user` has no `~` component, so it is left as a relative path.
+        match path.strip_prefix("~") {
+            Ok(rest) => self.home_dir.join(rest),
+            Err(_) => path,
+        }
+    }
+
+    pub(super) fn env_var_or_default(&self, env_var: &str, default: impl AsRef<Path>) -> PathBuf {
+        self.env_var_or_none(env_var)
+            .unwrap_or_else(|| self.home_dir.join(default))
+    }
+
+    // User folders can be set through environment variables, or through `user-dirs.dirs` in the configuration directory.
+    fn user_dir(&self, env_var: &str, default: &str) -> Option<PathBuf> {
+        if let Some(path) = self.env_var_or_none(env_var) {
+            return Some(path);
+        }
+
+        let user_dirs = self.base_dir(BaseDir::Config).join("user-dirs.dirs");
+        let entry = std::fs::read_to_string(user_dirs)
+            .ok()
+            .and_then(|contents| user_dirs::lookup(&contents, env_var, &self.home_dir));
+
+        match entry {
+            Some(user_dirs::Entry::Path(path)) => Some(path),
+            Some(user_dirs::Entry::Disabled) => None,
+            None => Some(self.home_dir.join(default)),
+        }
+    }
+
+    fn base_dir(&self, dir: BaseDir) -> PathBuf {
+        self.env_var_or_default(dir.env_var(), dir.default_path())
+    }
+
+    fn base_dir_with_source(&self, dir: BaseDir) -> (PathBuf, DirSource) {
+        match self.env_var_or_none(dir.env_var()) {
+            Some(path) => (path, DirSource::EnvVar(dir.env_var())),
+            None => (self.home_dir.join(dir.default_path()), DirSource::Default),
+        }
+    }
+
+    fn search_dirs(&self, dirs: SearchDirs) -> Vec<PathBuf> {
+        // Empty & relative entries must be ignored, as if they weren’t in the list at all.
+        let paths: Vec<PathBuf> = self
+            .env
+            .var_os(dirs.env_var())
+            .map(|paths| {
+                std::env::split_paths(&paths)
+                    .filter(|path| path.is_absolute())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if paths.is_empty() {
+            self.default_search_dirs(dirs)
+        } else {
+            paths
+        }
+    }
+
+    // The defaults of the XDG spec are meaningless outside of Linux & the BSDs, so the platform’s own system-wide folders are searched there instead.
+    fn default_search_dirs(&self, dirs: SearchDirs) -> Vec<PathBuf> {
+        cfg_if::cfg_if! {
+            if #[cfg(all(windows, feature = "windows"))] {
+                let _ = dirs;
+                super::windows::program_data_dir(&self.env).into_iter().collect()
+            } else if #[cfg(windows)] {
+                // Without the Windows strategy, the Known Folder cannot be looked up.
+                let _ = dirs;
+                self.env
+                    .var_os("ProgramData")
+                    .filter(|path| !path.is_empty())
+                    .map(PathBuf::from)
+                    .into_iter()
+                    .collect()
+            } else if #[cfg(any(target_os = "macos", target_os = "ios"))] {
+                match dirs {
+                    SearchDirs::Config => vec![PathBuf::from("/Library/Preferences/")],
+                    SearchDirs::Data => vec![PathBuf::from("/Library/Application Support/")],
+                }
+            } else {
+                dirs.defaults().iter().map(PathBuf::from).collect()
+            }
+        }
+    }
+
+    /// Gets the preference-ordered list of system-wide configuration directories from `XDG_CONFIG_DIRS`.
+    /// This defaults to `/etc/xdg/` when the variable is unset or contains no absolute paths. As that folder is meaningless elsewhere, the default is `/Library/Preferences/` on macOS & iOS, and the `ProgramData` folder (usually `C:\ProgramData`) on Windows.
+    ///
+    /// The entries are separated like those of `PATH`, i.e. by `;` on Windows & by `:` everywhere else, so the colons of drive letters don’t split a Windows path in two. Empty and relative entries are ignored, as required by the XDG spec:
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::collections::HashMap;
+    /// use std::path::PathBuf;
+    ///
+    /// let (sep, a, b) = if cfg!(windows) {
+    ///     (";", "C:\\a\\", "C:\\b\\")
+    /// } else {
+    ///     (":", "/a/", "/b/")
+    /// };
+    ///
+    /// // Leading, doubled & trailing separators, and a relative entry.
+    /// let value = format!("{sep}{a}{sep}{sep}relative{sep}{b}{sep}");
+    /// let base_strategy = Xdg::new_with_env(HashMap::from([("XDG_CONFIG_DIRS", value)])).unwrap();
+    ///
+    /// assert_eq!(base_strategy.config_dirs(), vec![PathBuf::from(a), PathBuf::from(b)]);
+    ///
+    /// // The colons of drive letters don’t split a Windows path in two.
+    /// if cfg!(windows) {
+    ///     let value = "C:\\a;D:\\b";
+    ///     let base_strategy = Xdg::new_with_env(HashMap::from([("XDG_CONFIG_DIRS", value)])).unwrap();
+    ///
+    ///     assert_eq!(
+    ///         base_strategy.config_dirs(),
+    ///         vec![PathBuf::from("C:\\a"), PathBuf::from("D:\\b")]
+    ///     );
+    /// }
+    ///
+    /// // We get the default value if no entries are usable.
+    /// let value = format!("{sep}{sep}relative{sep}");
+    /// let base_strategy = Xdg::new_with_env(HashMap::from([("XDG_CONFIG_DIRS", value)])).unwrap();
+    ///
+    /// if cfg!(any(target_os = "macos", target_os = "ios")) {
+    ///     assert_eq!(base_strategy.config_dirs(), vec![PathBuf::from("/Library/Preferences/")]);
+    /// } else if cfg!(unix) {
+    ///     assert_eq!(base_strategy.config_dirs(), vec![PathBuf::from("/etc/xdg/")]);
+    /// }
+    /// ```
+    pub fn config_dirs(&self) -> Vec<PathBuf> {
+        self.search_dirs(SearchDirs::Config)
+    }
+
+    /// Gets the preference-ordered list of system-wide data directories from `XDG_DATA_DIRS`.
+    /// This defaults to `/usr/local/share/` & `/usr/share/` when the variable is unset or contains no absolute paths. As those folders are meaningless elsewhere, the default is `/Library/Application Support/` on macOS & iOS, and the `ProgramData` folder (usually `C:\ProgramData`) on Windows.
+    ///
+    /// The entries are separated like those of [`XDG_CONFIG_DIRS`](#method.config_dirs). Empty and relative entries are ignored, as required by the XDG spec:
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::collections::HashMap;
+    /// use std::path::PathBuf;
+    ///
+    /// let (sep, a, b) = if cfg!(windows) {
+    ///     (";", "C:\\a\\", "C:\\b\\")
+    /// } else {
+    ///     (":", "/a/", "/b/")
+    /// };
+    ///
+    /// let value = format!("{sep}{a}{sep}{sep}relative{sep}{b}{sep}");
+    /// let base_strategy = Xdg::new_with_env(HashMap::from([("XDG_DATA_DIRS", value)])).unwrap();
+    ///
+    /// assert_eq!(base_strategy.data_dirs(), vec![PathBuf::from(a), PathBuf::from(b)]);
+    ///
+    /// let base_strategy = Xdg::new_with_env(HashMap::<String, String>::new()).unwrap();
+    ///
+    /// if cfg!(any(target_os = "macos", target_os = "ios")) {
+    ///     assert_eq!(
+    ///         base_strategy.data_dirs(),
+    ///         vec![PathBuf::from("/Library/Application Support/")]
+    ///     );
+    /// } else if cfg!(unix) {
+    ///     assert_eq!(
+    ///         base_strategy.data_dirs(),
+    ///         vec![PathBuf::from("/usr/local/share/"), PathBuf::from("/usr/share/")]
+    ///     );
+    /// }
+    /// ```
+    pub fn data_dirs(&self) -> Vec<PathBuf> {
+        self.search_dirs(SearchDirs::Data)
     }
 }
 
-impl super::BaseStrategy for Xdg {
+impl<E: EnvProvider> super::BaseStrategy for Xdg<E> {
     fn home_dir(&self) -> &Path {
         &self.home_dir
     }
 
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.env.var_os(key)
+    }
+
     fn config_dir(&self) -> PathBuf {
-        self.env_var_or_default("XDG_CONFIG_HOME", ".config/")
+        self.base_dir(BaseDir::Config)
     }
 
     fn data_dir(&self) -> PathBuf {
-        self.env_var_or_default("XDG_DATA_HOME", ".local/share/")
+        self.base_dir(BaseDir::Data)
     }
 
     fn cache_dir(&self) -> PathBuf {
-        self.env_var_or_default("XDG_CACHE_HOME", ".cache/")
+        self.base_dir(BaseDir::Cache)
     }
 
     fn state_dir(&self) -> Option<PathBuf> {
-        Some(self.env_var_or_default("XDG_STATE_HOME", ".local/state/"))
+        Some(self.base_dir(BaseDir::State))
     }
 
     fn runtime_dir(&self) -> Option<PathBuf> {
-        Self::env_var_or_none("XDG_RUNTIME_DIR")
+        self.env_var_or_none("XDG_RUNTIME_DIR")
+            .filter(|runtime_dir| !self.validate_runtime_dir || is_valid_runtime_dir(runtime_dir))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_dir(BaseDir::Bin)
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.base_dir_with_source(BaseDir::Config)
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.base_dir_with_source(BaseDir::Data)
     }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.base_dir_with_source(BaseDir::Cache)
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        Some(self.base_dir_with_source(BaseDir::State))
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.runtime_dir()
+            .map(|dir| (dir, DirSource::EnvVar("XDG_RUNTIME_DIR")))
+    }
+
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_DESKTOP_DIR", "Desktop/")
+    }
+
+    fn documents_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_DOCUMENTS_DIR", "Documents/")
+    }
+
+    fn downloads_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_DOWNLOAD_DIR", "Downloads/")
+    }
+
+    fn music_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_MUSIC_DIR", "Music/")
+    }
+
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_PICTURES_DIR", "Pictures/")
+    }
+
+    fn public_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_PUBLICSHARE_DIR", "Public/")
+    }
+
+    fn templates_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_TEMPLATES_DIR", "Templates/")
+    }
+
+    fn videos_dir(&self) -> Option<PathBuf> {
+        self.user_dir("XDG_VIDEOS_DIR", "Videos/")
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        Some(self.base_dir(BaseDir::State).join("logs"))
+    }
+}
+
+// The XDG spec requires the runtime directory to be owned by the current user, and to be inaccessible to everyone else.
+#[cfg(unix)]
+fn is_valid_runtime_dir(runtime_dir: &Path) -> bool {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    std::fs::metadata(runtime_dir).is_ok_and(|metadata| {
+        metadata.is_dir()
+            && metadata.uid() == crate::effective_uid()
+            && metadata.permissions().mode() & 0o777 == 0o700
+    })
+}
+
+#[cfg(not(unix))]
+fn is_valid_runtime_dir(_runtime_dir: &Path) -> bool {
+    true
+}
+
+// Shell variable names start with a letter or an underscore, followed by letters, digits & underscores.
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
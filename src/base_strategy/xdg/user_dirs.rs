@@ -0,0 +1,67 @@
+//! A parser for the `user-dirs.dirs` file written by [`xdg-user-dirs-update`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/).
+//!
+//! Each line of the file looks like `XDG_DOWNLOAD_DIR="$HOME/Downloads"`, where the quoted value is either a shell-escaped path relative to `$HOME`, or an absolute path. No other format is supported.
+
+use std::path::{Path, PathBuf};
+
+/// The value of a key in a `user-dirs.dirs` file.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Entry {
+    Path(PathBuf),
+    /// `xdg-user-dirs` disables a folder by pointing it at the home directory itself.
+    Disabled,
+}
+
+/// Looks up `key` in the contents of a `user-dirs.dirs` file, expanding `$HOME` to `home_dir`.
+/// As the file is meant to be sourced by a shell, the last valid assignment wins.
+pub(super) fn lookup(contents: &str, key: &str, home_dir: &Path) -> Option<Entry> {
+    contents
+        .lines()
+        .rev()
+        .filter_map(|line| parse_line(line, home_dir))
+        .find(|(line_key, _)| *line_key == key)
+        .map(|(_, path)| {
+            if path == home_dir {
+                Entry::Disabled
+            } else {
+                Entry::Path(path)
+            }
+        })
+}
+
+fn parse_line<'a>(line: &'a str, home_dir: &Path) -> Option<(&'a str, PathBuf)> {
+    // `trim` also takes care of the `\r` left over by CRLF line endings.
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (key, value) = line.split_once('=')?;
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+
+    // `$HOME` must be checked for before unescaping, as `\$HOME` is a literal folder name.
+    let path = match value.strip_prefix("$HOME") {
+        Some("") => home_dir.to_path_buf(),
+        Some(rest) => home_dir.join(unescape(rest.strip_prefix('/')?)?),
+        None => PathBuf::from(unescape(value)?),
+    };
+
+    path.is_absolute().then_some((key.trim(), path))
+}
+
+// Removes the backslashes the file uses to escape special characters, e.g. `\"` or `\$`.
+fn unescape(value: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.push(chars.next()?),
+            // An unescaped quote means that this isn’t a single quoted value.
+            '"' => return None,
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(unescaped)
+}
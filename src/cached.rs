@@ -0,0 +1,246 @@
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::app_strategy::AppStrategy;
+use crate::base_strategy::BaseStrategy;
+use crate::DirSource;
+
+/// Wraps a [`BaseStrategy`](base_strategy/trait.BaseStrategy.html) or an [`AppStrategy`](app_strategy/trait.AppStrategy.html), and remembers every directory the first time it is computed.
+///
+/// The strategies read the environment (and sometimes files, such as `user-dirs.dirs`) on every call, so this is useful if you call them in hot loops. If the environment changes afterwards, call [`invalidate`](#method.invalidate) to compute the directories again.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::base_strategy::{BaseStrategy, Xdg};
+/// use etcetera::Cached;
+/// use std::path::Path;
+///
+/// let config_path = if cfg!(windows) {
+///     "C:\\my_config_location\\"
+/// } else {
+///     "/my_config_location/"
+/// };
+///
+/// std::env::set_var("XDG_CONFIG_HOME", config_path);
+/// let mut base_strategy = Cached::new(Xdg::new().unwrap());
+/// assert_eq!(base_strategy.config_dir(), Path::new(config_path));
+///
+/// // The cached value is still returned after the environment changes…
+/// std::env::remove_var("XDG_CONFIG_HOME");
+/// assert_eq!(base_strategy.config_dir(), Path::new(config_path));
+///
+/// // …until the cache is invalidated.
+/// base_strategy.invalidate();
+/// assert_eq!(base_strategy.config_dir(), base_strategy.home_dir().join(".config/"));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cached<S> {
+    strategy: S,
+    dirs: Dirs,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Dirs {
+    config_dir: OnceLock<PathBuf>,
+    data_dir: OnceLock<PathBuf>,
+    local_data_dir: OnceLock<PathBuf>,
+    cache_dir: OnceLock<PathBuf>,
+    state_dir: OnceLock<Option<PathBuf>>,
+    runtime_dir: OnceLock<Option<PathBuf>>,
+    log_dir: OnceLock<Option<PathBuf>>,
+    bin_dir: OnceLock<PathBuf>,
+    preference_dir: OnceLock<PathBuf>,
+    secret_dir: OnceLock<Option<PathBuf>>,
+    app_root: OnceLock<Option<PathBuf>>,
+    app_bin_dir: OnceLock<Option<PathBuf>>,
+    config_dirs: OnceLock<Vec<PathBuf>>,
+    data_dirs: OnceLock<Vec<PathBuf>>,
+    temp_dir: OnceLock<PathBuf>,
+    desktop_dir: OnceLock<Option<PathBuf>>,
+    documents_dir: OnceLock<Option<PathBuf>>,
+    downloads_dir: OnceLock<Option<PathBuf>>,
+    music_dir: OnceLock<Option<PathBuf>>,
+    pictures_dir: OnceLock<Option<PathBuf>>,
+    public_dir: OnceLock<Option<PathBuf>>,
+    templates_dir: OnceLock<Option<PathBuf>>,
+    videos_dir: OnceLock<Option<PathBuf>>,
+}
+
+impl<S> Cached<S> {
+    /// Wraps `strategy`. Nothing is computed until a directory is requested.
+    pub fn new(strategy: S) -> Self {
+        Self {
+            strategy,
+            dirs: Dirs::default(),
+        }
+    }
+
+    /// Forgets every directory computed so far, so that they are computed again on their next use.
+    pub fn invalidate(&mut self) {
+        self.dirs = Dirs::default();
+    }
+
+    /// Gets a reference to the wrapped strategy.
+    pub fn get_ref(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Unwraps the strategy, discarding the cache.
+    pub fn into_inner(self) -> S {
+        self.strategy
+    }
+}
+
+macro_rules! cached {
+    ($($name: ident -> $ret: ty),* $(,)?) => {
+        $(
+            fn $name(&self) -> $ret {
+                self.dirs
+                    .$name
+                    .get_or_init(|| self.strategy.$name())
+                    .clone()
+            }
+        )*
+    };
+}
+
+// The cached paths outlive the borrow of `self`, so they can be lent out instead of cloned.
+macro_rules! cached_cow {
+    ($($name: ident => $dir: ident),* $(,)?) => {
+        $(
+            fn $name(&self) -> Cow<'_, Path> {
+                Cow::Borrowed(self.dirs.$dir.get_or_init(|| self.strategy.$dir()))
+            }
+        )*
+    };
+}
+
+impl<S: BaseStrategy> BaseStrategy for Cached<S> {
+    fn home_dir(&self) -> &Path {
+        self.strategy.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.strategy.env_var_os(key)
+    }
+
+    cached!(
+        config_dir -> PathBuf,
+        data_dir -> PathBuf,
+        local_data_dir -> PathBuf,
+        cache_dir -> PathBuf,
+        state_dir -> Option<PathBuf>,
+        runtime_dir -> Option<PathBuf>,
+        log_dir -> Option<PathBuf>,
+        bin_dir -> PathBuf,
+        desktop_dir -> Option<PathBuf>,
+        documents_dir -> Option<PathBuf>,
+        downloads_dir -> Option<PathBuf>,
+        music_dir -> Option<PathBuf>,
+        pictures_dir -> Option<PathBuf>,
+        public_dir -> Option<PathBuf>,
+        templates_dir -> Option<PathBuf>,
+        videos_dir -> Option<PathBuf>,
+    );
+
+    cached_cow!(
+        config_dir_cow => config_dir,
+        data_dir_cow => data_dir,
+        cache_dir_cow => cache_dir,
+    );
+
+    // This touches the filesystem, so it must not be cached.
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        self.strategy.runtime_dir_or_fallback()
+    }
+
+    // The sources are for diagnostics, so they always reflect the current environment.
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.strategy.config_dir_with_source()
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.strategy.data_dir_with_source()
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.strategy.cache_dir_with_source()
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.strategy.state_dir_with_source()
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.strategy.runtime_dir_with_source()
+    }
+}
+
+impl<S: AppStrategy> AppStrategy for Cached<S> {
+    fn home_dir(&self) -> &Path {
+        self.strategy.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.strategy.env_var_os(key)
+    }
+
+    cached!(
+        config_dir -> PathBuf,
+        data_dir -> PathBuf,
+        local_data_dir -> PathBuf,
+        cache_dir -> PathBuf,
+        state_dir -> Option<PathBuf>,
+        runtime_dir -> Option<PathBuf>,
+        log_dir -> Option<PathBuf>,
+        bin_dir -> PathBuf,
+        preference_dir -> PathBuf,
+        secret_dir -> Option<PathBuf>,
+        app_root -> Option<PathBuf>,
+        app_bin_dir -> Option<PathBuf>,
+        config_dirs -> Vec<PathBuf>,
+        data_dirs -> Vec<PathBuf>,
+        temp_dir -> PathBuf,
+    );
+
+    cached_cow!(
+        config_dir_cow => config_dir,
+        data_dir_cow => data_dir,
+        cache_dir_cow => cache_dir,
+    );
+
+    // These touch the filesystem, so they must not be cached.
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        self.strategy.runtime_dir_or_fallback()
+    }
+
+    fn scratch_dir(&self) -> io::Result<PathBuf> {
+        self.strategy.scratch_dir()
+    }
+
+    // The sources are for diagnostics, so they always reflect the current environment.
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.strategy.config_dir_with_source()
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.strategy.data_dir_with_source()
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.strategy.cache_dir_with_source()
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.strategy.state_dir_with_source()
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.strategy.runtime_dir_with_source()
+    }
+}
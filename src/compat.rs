@@ -0,0 +1,146 @@
+//! A drop-in replacement for the `ProjectDirs` type of the [`directories`](https://docs.rs/directories) crate, to ease migrating from it.
+//!
+//! [`ProjectDirs`](struct.ProjectDirs.html) has the same constructor & method names as its namesake, but its directories come from the native [`AppStrategy`](../app_strategy/trait.AppStrategy.html) of the current OS, see [`choose_native_strategy_enum`](../app_strategy/fn.choose_native_strategy_enum.html). Once nothing depends on the `directories` naming anymore, switch to the strategy itself, which is available through [`strategy`](struct.ProjectDirs.html#method.strategy).
+//!
+//! The methods map onto etcetera as follows:
+//!
+//! | `directories::ProjectDirs` | etcetera                                                                                   |
+//! |----------------------------|--------------------------------------------------------------------------------------------|
+//! | `from`                     | [`AppStrategyArgs`](../app_strategy/struct.AppStrategyArgs.html) & `choose_native_strategy` |
+//! | `cache_dir`                | [`AppStrategy::cache_dir`](../app_strategy/trait.AppStrategy.html#tymethod.cache_dir)       |
+//! | `config_dir`               | [`AppStrategy::config_dir`](../app_strategy/trait.AppStrategy.html#tymethod.config_dir)     |
+//! | `config_local_dir`         | [`AppStrategy::config_dir`](../app_strategy/trait.AppStrategy.html#tymethod.config_dir)     |
+//! | `data_dir`                 | [`AppStrategy::data_dir`](../app_strategy/trait.AppStrategy.html#tymethod.data_dir)         |
+//! | `data_local_dir`           | [`AppStrategy::local_data_dir`](../app_strategy/trait.AppStrategy.html#method.local_data_dir) |
+//! | `preference_dir`           | [`AppStrategy::preference_dir`](../app_strategy/trait.AppStrategy.html#method.preference_dir) |
+//! | `runtime_dir`              | [`AppStrategy::runtime_dir`](../app_strategy/trait.AppStrategy.html#tymethod.runtime_dir)   |
+//! | `state_dir`                | [`AppStrategy::state_dir`](../app_strategy/trait.AppStrategy.html#tymethod.state_dir)       |
+//!
+//! etcetera has no separate local configuration directory, so `config_local_dir` is the same as `config_dir`. `project_path` has no equivalent, as the folder names differ between the strategies.
+//!
+//! The functions of the `dirs` crate & the methods of `directories::BaseDirs` map onto the methods of [`BaseStrategy`](../base_strategy/trait.BaseStrategy.html) with the same names in the same way.
+//!
+//! The layouts don’t match those of `directories` exactly, e.g. on Linux, `directories` removes the spaces from the application’s name, while etcetera replaces them with hyphens: `~/.config/frobnicatorplus/` rather than `~/.config/frobnicator-plus/`. Existing files can be moved over with [`AppStrategy::migrate_from`](../app_strategy/trait.AppStrategy.html#method.migrate_from).
+//!
+//! ```
+//! use etcetera::app_strategy::{choose_native_strategy, AppStrategy, AppStrategyArgs};
+//! use etcetera::compat::project_dirs_from;
+//!
+//! let project_dirs = project_dirs_from("org", "Acme Corp", "Frobnicator Plus").unwrap();
+//!
+//! let app_strategy = choose_native_strategy(AppStrategyArgs {
+//!     top_level_domain: "org".to_string(),
+//!     author: "Acme Corp".to_string(),
+//!     app_name: "Frobnicator Plus".to_string(),
+//!     ..Default::default()
+//! }).unwrap();
+//!
+//! assert_eq!(project_dirs.config_dir(), app_strategy.config_dir());
+//! assert_eq!(project_dirs.config_local_dir(), app_strategy.config_dir());
+//! assert_eq!(project_dirs.data_local_dir(), app_strategy.local_data_dir());
+//! assert_eq!(project_dirs.state_dir(), app_strategy.state_dir().as_deref());
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::app_strategy::{
+    choose_native_strategy_enum, AnyAppStrategy, AppStrategy, AppStrategyArgs,
+};
+
+/// The directories of an application, named like those of `directories::ProjectDirs`. See the [module documentation](index.html) for how they map onto etcetera.
+#[derive(Debug, Clone)]
+pub struct ProjectDirs {
+    strategy: AnyAppStrategy,
+    cache_dir: PathBuf,
+    config_dir: PathBuf,
+    data_dir: PathBuf,
+    data_local_dir: PathBuf,
+    preference_dir: PathBuf,
+    runtime_dir: Option<PathBuf>,
+    state_dir: Option<PathBuf>,
+}
+
+impl ProjectDirs {
+    /// Computes the directories of the application `application` by `organization`, whose domain ends in `qualifier`, e.g. `ProjectDirs::from("org", "Acme Corp", "Frobnicator Plus")`.
+    ///
+    /// Like its namesake, this returns `None` if the home directory cannot be located. It also returns `None` if the names cannot be used as folder names.
+    pub fn from(qualifier: &str, organization: &str, application: &str) -> Option<Self> {
+        let strategy = choose_native_strategy_enum(AppStrategyArgs {
+            top_level_domain: qualifier.to_string(),
+            author: organization.to_string(),
+            app_name: application.to_string(),
+            ..Default::default()
+        })
+        .ok()?;
+
+        Some(Self {
+            cache_dir: strategy.cache_dir(),
+            config_dir: strategy.config_dir(),
+            data_dir: strategy.data_dir(),
+            data_local_dir: strategy.local_data_dir(),
+            preference_dir: strategy.preference_dir(),
+            runtime_dir: strategy.runtime_dir(),
+            state_dir: strategy.state_dir(),
+            strategy,
+        })
+    }
+
+    /// Gets the strategy the directories were computed with.
+    pub fn strategy(&self) -> &AnyAppStrategy {
+        &self.strategy
+    }
+
+    /// Unwraps the strategy the directories were computed with.
+    pub fn into_strategy(self) -> AnyAppStrategy {
+        self.strategy
+    }
+
+    /// Gets the cache directory.
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Gets the configuration directory.
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    /// Gets the local configuration directory, which is the same as [`config_dir`](#method.config_dir).
+    pub fn config_local_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    /// Gets the data directory.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Gets the local data directory.
+    pub fn data_local_dir(&self) -> &Path {
+        &self.data_local_dir
+    }
+
+    /// Gets the preference directory.
+    pub fn preference_dir(&self) -> &Path {
+        &self.preference_dir
+    }
+
+    /// Gets the runtime directory, if the strategy has one.
+    pub fn runtime_dir(&self) -> Option<&Path> {
+        self.runtime_dir.as_deref()
+    }
+
+    /// Gets the state directory, if the strategy has one.
+    pub fn state_dir(&self) -> Option<&Path> {
+        self.state_dir.as_deref()
+    }
+}
+
+/// Computes the directories of an application. This is the same as [`ProjectDirs::from`](struct.ProjectDirs.html#method.from).
+pub fn project_dirs_from(
+    qualifier: &str,
+    organization: &str,
+    application: &str,
+) -> Option<ProjectDirs> {
+    ProjectDirs::from(qualifier, organization, application)
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+/// Selects one of the directories of a strategy, e.g. for [`BaseStrategy::dir`](base_strategy/trait.BaseStrategy.html#method.dir) or [`AppStrategy::find_in_dirs`](app_strategy/trait.AppStrategy.html#method.find_in_dirs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum DirKind {
+    /// The home directory.
+    Home,
+    /// The configuration directory, followed by the system-wide configuration directories when searching.
+    Config,
+    /// The data directory, followed by the system-wide data directories when searching.
+    Data,
+    /// The cache directory.
+    Cache,
+    /// The state directory.
+    State,
+    /// The runtime directory.
+    Runtime,
+    /// The log directory.
+    Log,
+    /// The binary directory.
+    Bin,
+}
+
+impl DirKind {
+    /// Every kind of directory, in the order they are declared in.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::base_strategy::{BaseStrategy, Xdg};
+    /// use etcetera::DirKind;
+    ///
+    /// let base_strategy = Xdg::new().unwrap();
+    ///
+    /// for kind in DirKind::ALL {
+    ///     if let Some(dir) = base_strategy.dir(kind) {
+    ///         assert!(dir.is_absolute(), "{kind:?}");
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub const ALL: [Self; 8] = [
+        Self::Home,
+        Self::Config,
+        Self::Data,
+        Self::Cache,
+        Self::State,
+        Self::Runtime,
+        Self::Log,
+        Self::Bin,
+    ];
+}
+
+/// Which directory of a strategy a path lies in. This is returned by [`ResolvedDirs::classify`](struct.ResolvedDirs.html#method.classify) and the `classify` methods of the strategies.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirClassification {
+    /// The kind of the directory containing the path.
+    pub kind: DirKind,
+    /// The directory containing the path.
+    pub dir: PathBuf,
+    /// The path, relative to `dir`. This is empty if the path is the directory itself.
+    pub relative: PathBuf,
+}
+
+/// Whether a configuration directory belongs to the current user or is shared by the whole system. This is returned by [`AppStrategy::config_search_path`](app_strategy/trait.AppStrategy.html#method.config_search_path).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ConfigScope {
+    /// The current user’s configuration directory.
+    User,
+    /// One of the system-wide configuration directories.
+    System,
+}
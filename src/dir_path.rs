@@ -0,0 +1,107 @@
+use std::borrow::Borrow;
+use std::ffi::OsStr;
+use std::ops::{Deref, Div};
+use std::path::{Path, PathBuf};
+
+/// A `PathBuf` which can be extended with the `/` operator, e.g. `DirPath::from(strategy.config_dir()) / "themes" / "dark.toml"`, instead of chaining `join` calls.
+///
+/// It dereferences to a [`Path`], so it can be used wherever a `&Path` is expected. The strategies’ accessors still return a plain `PathBuf`, so that existing code keeps compiling; convert their results with `From`.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+/// use etcetera::DirPath;
+/// use std::path::PathBuf;
+///
+/// let app_strategy = Xdg::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// }).unwrap();
+///
+/// let config_dir = DirPath::from(app_strategy.config_dir());
+/// let theme = &config_dir / "themes" / "dark.toml";
+///
+/// assert_eq!(theme, app_strategy.config_dir().join("themes").join("dark.toml"));
+/// assert_eq!(theme.extension(), Some("toml".as_ref()));
+/// assert_eq!(PathBuf::from(config_dir), app_strategy.config_dir());
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirPath(PathBuf);
+
+impl DirPath {
+    /// Unwraps the `PathBuf`.
+    pub fn into_path_buf(self) -> PathBuf {
+        self.0
+    }
+}
+
+impl Deref for DirPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for DirPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<OsStr> for DirPath {
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_os_str()
+    }
+}
+
+impl Borrow<Path> for DirPath {
+    fn borrow(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl From<PathBuf> for DirPath {
+    fn from(path: PathBuf) -> Self {
+        Self(path)
+    }
+}
+
+impl From<DirPath> for PathBuf {
+    fn from(path: DirPath) -> Self {
+        path.0
+    }
+}
+
+impl PartialEq<Path> for DirPath {
+    fn eq(&self, other: &Path) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<PathBuf> for DirPath {
+    fn eq(&self, other: &PathBuf) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<P: AsRef<Path>> Div<P> for DirPath {
+    type Output = DirPath;
+
+    fn div(mut self, path: P) -> DirPath {
+        self.0.push(path);
+        self
+    }
+}
+
+impl<P: AsRef<Path>> Div<P> for &DirPath {
+    type Output = DirPath;
+
+    fn div(self, path: P) -> DirPath {
+        DirPath(self.0.join(path))
+    }
+}
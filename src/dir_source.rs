@@ -0,0 +1,55 @@
+use std::fmt;
+
+/// Where a directory came from. This is returned by the `*_with_source` methods of [`BaseStrategy`](base_strategy/trait.BaseStrategy.html#method.config_dir_with_source) & [`AppStrategy`](app_strategy/trait.AppStrategy.html#method.config_dir_with_source), e.g. to explain in verbose output why a directory is where it is.
+///
+/// Its `Display` implementation names the source, so that you can write messages like “config_dir is `~/.config` (from $XDG_CONFIG_HOME)”.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::base_strategy::{BaseStrategy, Xdg};
+/// use etcetera::DirSource;
+/// use std::collections::HashMap;
+/// use std::path::Path;
+///
+/// let config_path = if cfg!(windows) {
+///     "C:\\my_config_location\\"
+/// } else {
+///     "/my_config_location/"
+/// };
+///
+/// let base_strategy = Xdg::new_with_env(HashMap::from([("XDG_CONFIG_HOME", config_path)])).unwrap();
+///
+/// let (config_dir, source) = base_strategy.config_dir_with_source();
+/// assert_eq!(config_dir, Path::new(config_path));
+/// assert_eq!(source, DirSource::EnvVar("XDG_CONFIG_HOME"));
+/// assert_eq!(source.to_string(), "$XDG_CONFIG_HOME");
+///
+/// let (_, source) = base_strategy.data_dir_with_source();
+/// assert_eq!(source, DirSource::Default);
+/// assert_eq!(source.to_string(), "the default");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum DirSource {
+    /// The environment variable with this name.
+    EnvVar(&'static str),
+    /// The strategy’s own default, e.g. `~/.config/` for XDG, or the Known Folder on Windows.
+    Default,
+    /// A directory that was passed explicitly, e.g. through [`Override`](struct.Override.html).
+    Override,
+    /// A last-resort guess, used because the usual lookups failed, e.g. `~\AppData\Roaming` on Windows when neither the Known Folder nor `APPDATA` are available.
+    Fallback,
+}
+
+impl fmt::Display for DirSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EnvVar(env_var) => write!(f, "${env_var}"),
+            Self::Default => f.write_str("the default"),
+            Self::Override => f.write_str("an override"),
+            Self::Fallback => f.write_str("a fallback"),
+        }
+    }
+}
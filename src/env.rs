@@ -0,0 +1,254 @@
+//! The strategies read environment variables through the [`EnvProvider`](trait.EnvProvider.html) trait, so that you can supply your own values instead of mutating the process’ environment.
+//!
+//! ```
+//! # #[cfg(feature = "xdg")]
+//! # {
+//! use etcetera::base_strategy::BaseStrategy;
+//! use etcetera::base_strategy::Xdg;
+//! use std::collections::HashMap;
+//! use std::path::Path;
+//!
+//! let config_path = if cfg!(windows) {
+//!     "C:\\my_config_location\\"
+//! } else {
+//!     "/my_config_location/"
+//! };
+//!
+//! let env = HashMap::from([("XDG_CONFIG_HOME", config_path)]);
+//! let base_strategy = Xdg::new_with_env(env).unwrap();
+//!
+//! assert_eq!(base_strategy.config_dir(), Path::new(config_path));
+//!
+//! // Variables missing from the map are treated as unset.
+//! let home_dir = etcetera::home_dir().unwrap();
+//! assert_eq!(
+//!     base_strategy.data_dir().strip_prefix(&home_dir),
+//!     Ok(Path::new(".local/share/"))
+//! );
+//! # }
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString};
+use std::hash::{BuildHasher, Hash};
+use std::io;
+use std::path::Path;
+
+/// Provides the values of environment variables to a strategy.
+///
+/// Values are `OsString`s rather than `String`s, as Unix allows paths which aren’t valid UTF-8. The strategies turn them into paths as-is, so such a directory is used rather than silently replaced by the default:
+///
+/// ```
+/// # #[cfg(all(unix, feature = "xdg"))]
+/// # {
+/// use etcetera::base_strategy::{BaseStrategy, Xdg};
+/// use std::collections::HashMap;
+/// use std::ffi::OsStr;
+/// use std::os::unix::ffi::OsStrExt;
+/// use std::path::Path;
+///
+/// let config_path = OsStr::from_bytes(b"/home/me/conf\xff");
+///
+/// let base_strategy = Xdg::new_with_env(HashMap::from([("XDG_CONFIG_HOME", config_path)])).unwrap();
+/// assert_eq!(base_strategy.config_dir(), Path::new(config_path));
+/// # }
+/// ```
+pub trait EnvProvider {
+    /// Gets the value of the environment variable `key`, or `None` if it isn’t set.
+    fn var_os(&self, key: &str) -> Option<OsString>;
+}
+
+/// Reads environment variables from the current process using [`std::env::var_os`]. This is what the strategies use by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StdEnv;
+
+impl EnvProvider for StdEnv {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+}
+
+/// Provides no environment variables at all, so that a strategy only uses its default directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NoEnv;
+
+impl EnvProvider for NoEnv {
+    fn var_os(&self, _key: &str) -> Option<OsString> {
+        None
+    }
+}
+
+impl<K, V, S> EnvProvider for HashMap<K, V, S>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<OsStr>,
+    S: BuildHasher,
+{
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.get(key).map(|value| value.as_ref().to_os_string())
+    }
+}
+
+impl<K, V> EnvProvider for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<OsStr>,
+{
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.get(key).map(|value| value.as_ref().to_os_string())
+    }
+}
+
+impl<E: EnvProvider + ?Sized> EnvProvider for &E {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        (**self).var_os(key)
+    }
+}
+
+/// Reads the XDG variables from a `.env`-style file of `KEY=VALUE` lines, and everything else from another provider, i.e. the process’ environment by default. The file’s values take precedence.
+///
+/// This lets you keep a per-project directory layout in a file during development, rather than exporting the variables globally. Only keys starting with `XDG_` are read from the file, so that unrelated settings in a shared `.env` file are left alone.
+///
+/// Blank lines & lines starting with `#` are skipped, as is an `export ` prefix. Values may be wrapped in single or double quotes, which are removed, but are otherwise used verbatim: there are no escapes or variable substitutions.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::base_strategy::{BaseStrategy, Xdg};
+/// use etcetera::env::{DotEnv, EnvProvider};
+/// use std::path::Path;
+///
+/// let config_path = if cfg!(windows) { "C:\\project\\config" } else { "/project/config" };
+///
+/// let env = DotEnv::parse(&format!("
+///     # Development directories
+///     export XDG_CONFIG_HOME=\"{config_path}\"
+///     DATABASE_URL=postgres://localhost
+/// "));
+///
+/// let base_strategy = Xdg::new_with_env(env).unwrap();
+/// assert_eq!(base_strategy.config_dir(), Path::new(config_path));
+///
+/// // A missing file is treated like an empty one.
+/// let env = DotEnv::load("does/not/exist.env").unwrap();
+/// assert_eq!(env.var_os("XDG_CONFIG_HOME"), std::env::var_os("XDG_CONFIG_HOME"));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DotEnv<E = StdEnv> {
+    vars: BTreeMap<String, OsString>,
+    fallback: E,
+}
+
+impl DotEnv {
+    /// Reads the file at `path`, falling back to the process’ environment. A missing file is treated like an empty one, but other I/O errors are returned.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Parses the contents of a `.env` file, falling back to the process’ environment.
+    pub fn parse(contents: &str) -> Self {
+        let vars = contents
+            .lines()
+            .filter_map(parse_line)
+            .filter(|(key, _)| key.starts_with("XDG_"))
+            .map(|(key, value)| (key.to_string(), OsString::from(value)))
+            .collect();
+
+        Self {
+            vars,
+            fallback: StdEnv,
+        }
+    }
+}
+
+impl<E> DotEnv<E> {
+    /// Replaces the provider the variables missing from the file are read from.
+    pub fn with_fallback<F: EnvProvider>(self, fallback: F) -> DotEnv<F> {
+        DotEnv {
+            vars: self.vars,
+            fallback,
+        }
+    }
+}
+
+impl<E: EnvProvider> EnvProvider for DotEnv<E> {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        match self.vars.get(key) {
+            Some(value) => Some(value.clone()),
+            None => self.fallback.var_os(key),
+        }
+    }
+}
+
+/// Reads environment variables from the environment another process was started with, as listed in `/proc/<pid>/environ` on Linux. This is only available with the `proc-environ` feature.
+///
+/// Changes the process made to its environment after it started aren’t visible, and reading the file of a process owned by another user needs the corresponding privileges. Variables whose names aren’t valid UTF-8 are skipped.
+///
+/// ```
+/// use etcetera::env::{EnvProvider, ProcEnv};
+///
+/// let env = ProcEnv::parse(b"HOME=/home/me\0XDG_CONFIG_HOME=/home/me/conf\0");
+/// assert_eq!(env.var_os("XDG_CONFIG_HOME"), Some("/home/me/conf".into()));
+/// assert_eq!(env.var_os("XDG_DATA_HOME"), None);
+/// ```
+#[cfg(all(feature = "proc-environ", target_os = "linux"))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ProcEnv {
+    vars: BTreeMap<String, OsString>,
+}
+
+#[cfg(all(feature = "proc-environ", target_os = "linux"))]
+impl ProcEnv {
+    /// Reads the environment of the process with the ID `pid`.
+    pub fn load(pid: u32) -> io::Result<Self> {
+        std::fs::read(format!("/proc/{pid}/environ")).map(|contents| Self::parse(&contents))
+    }
+
+    /// Parses the contents of an `environ` file, i.e. `KEY=VALUE` entries separated by NUL bytes.
+    pub fn parse(contents: &[u8]) -> Self {
+        use std::os::unix::ffi::OsStrExt;
+
+        let vars = contents
+            .split(|&byte| byte == 0)
+            .filter_map(|entry| {
+                let separator = entry.iter().position(|&byte| byte == b'=')?;
+                let key = std::str::from_utf8(&entry[..separator]).ok()?;
+                let value = OsStr::from_bytes(&entry[separator + 1..]);
+                Some((key.to_string(), value.to_os_string()))
+            })
+            .collect();
+
+        Self { vars }
+    }
+}
+
+#[cfg(all(feature = "proc-environ", target_os = "linux"))]
+impl EnvProvider for ProcEnv {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.vars.get(key).cloned()
+    }
+}
+
+fn parse_line(line: &str) -> Option<(&str, &str)> {
+    // `trim` also takes care of the `\r` left over by CRLF line endings.
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, value) = line.split_once('=')?;
+    let value = value.trim();
+    let value = ['"', '\'']
+        .into_iter()
+        .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value);
+
+    Some((key.trim(), value))
+}
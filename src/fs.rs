@@ -0,0 +1,178 @@
+//! The `create_*_in` methods of [`AppStrategy`](../app_strategy/trait.AppStrategy.html) touch the filesystem through the [`FileSystem`](trait.FileSystem.html) trait, so that you can test your directory setup without creating real folders.
+//!
+//! ```
+//! # #[cfg(feature = "xdg")]
+//! # {
+//! use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+//! use etcetera::fs::{FileSystem, MemoryFs};
+//!
+//! let app_strategy = Xdg::new(AppStrategyArgs {
+//!     top_level_domain: "org".to_string(),
+//!     author: "Acme Corp".to_string(),
+//!     app_name: "Frobnicator Plus".to_string(),
+//!     ..Default::default()
+//! }).unwrap();
+//!
+//! let fs = MemoryFs::new();
+//! let config_dir = app_strategy.create_config_dir_in(&fs).unwrap();
+//!
+//! assert!(fs.exists(&config_dir));
+//! assert!(fs.exists(config_dir.parent().unwrap()));
+//! assert!(!fs.exists(&app_strategy.data_dir()));
+//! # }
+//! ```
+
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The Unix permissions the `create_*` methods give the state & runtime directories, so that only their owner can access them.
+pub const PRIVATE_DIR_MODE: u32 = 0o700;
+
+/// The Unix permissions the `create_*` methods give every other directory.
+pub const DEFAULT_DIR_MODE: u32 = 0o755;
+
+/// Creates & inspects directories on behalf of a strategy.
+pub trait FileSystem {
+    /// Creates `path` and any missing parents, like [`std::fs::create_dir_all`].
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    /// Creates `path` and any missing parents, giving `path` itself the Unix permissions `mode`, e.g. [`PRIVATE_DIR_MODE`](constant.PRIVATE_DIR_MODE.html). As usual, the process’ umask is applied on top. The parents get the default permissions, and the permissions of a directory which already exists are left alone.
+    ///
+    /// The default implementation ignores `mode` and calls [`create_dir_all`](#tymethod.create_dir_all), as does [`StdFs`](struct.StdFs.html) on other platforms than Unix.
+    fn create_dir_all_with_mode(&self, path: &Path, mode: u32) -> io::Result<()> {
+        let _ = mode;
+        self.create_dir_all(path)
+    }
+
+    /// Returns whether `path` exists, like [`Path::exists`].
+    fn exists(&self, path: &Path) -> bool;
+}
+
+/// Uses the real filesystem through [`std::fs`]. This is what the `create_*` methods use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StdFs;
+
+impl FileSystem for StdFs {
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(path)
+    }
+
+    #[cfg(unix)]
+    fn create_dir_all_with_mode(&self, path: &Path, mode: u32) -> io::Result<()> {
+        use std::os::unix::fs::DirBuilderExt;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        match std::fs::DirBuilder::new().mode(mode).create(path) {
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists && path.is_dir() => Ok(()),
+            result => result,
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+}
+
+/// Keeps track of the directories created through it in memory, without touching the real filesystem. Nothing exists until it has been created.
+#[derive(Debug, Default)]
+pub struct MemoryFs {
+    dirs: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl MemoryFs {
+    /// Creates an empty filesystem.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets every directory created so far, including the parents created along the way, sorted by path.
+    pub fn dirs(&self) -> Vec<PathBuf> {
+        self.lock().iter().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeSet<PathBuf>> {
+        // The set is always left in a consistent state, so a panic elsewhere doesn’t matter.
+        self.dirs.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl FileSystem for MemoryFs {
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let mut dirs = self.lock();
+        for ancestor in path.ancestors() {
+            if !ancestor.as_os_str().is_empty() {
+                dirs.insert(ancestor.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.lock().contains(path)
+    }
+}
+
+/// Records the directories that would be created on top of another filesystem, without creating any of them. This is what [`AppStrategy::plan_create`](../app_strategy/trait.AppStrategy.html#method.plan_create) uses.
+///
+/// A directory counts as existing if it exists in the wrapped filesystem or has already been planned, so each one is only planned once.
+#[derive(Debug, Default)]
+pub struct DryRunFs<F> {
+    fs: F,
+    planned: Mutex<Vec<PathBuf>>,
+}
+
+impl<F: FileSystem> DryRunFs<F> {
+    /// Plans on top of `fs`, e.g. [`StdFs`](struct.StdFs.html).
+    pub fn new(fs: F) -> Self {
+        Self {
+            fs,
+            planned: Mutex::default(),
+        }
+    }
+
+    /// Gets every directory that would have been created so far, in the order they would have been created in, i.e. parents first.
+    pub fn planned(&self) -> Vec<PathBuf> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<PathBuf>> {
+        // The list is always left in a consistent state, so a panic elsewhere doesn’t matter.
+        self.planned.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<F: FileSystem> FileSystem for DryRunFs<F> {
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let missing: Vec<&Path> = path
+            .ancestors()
+            .take_while(|ancestor| !ancestor.as_os_str().is_empty() && !self.exists(ancestor))
+            .collect();
+
+        self.lock()
+            .extend(missing.into_iter().rev().map(Path::to_path_buf));
+        Ok(())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.lock().iter().any(|planned| planned == path) || self.fs.exists(path)
+    }
+}
+
+impl<F: FileSystem + ?Sized> FileSystem for &F {
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        (**self).create_dir_all(path)
+    }
+
+    fn create_dir_all_with_mode(&self, path: &Path, mode: u32) -> io::Result<()> {
+        (**self).create_dir_all_with_mode(path, mode)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        (**self).exists(path)
+    }
+}
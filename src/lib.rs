@@ -8,6 +8,10 @@
 //! - Apple's [Standard Directories](https://developer.apple.com/library/content/documentation/FileManagement/Conceptual/FileSystemProgrammingGuide/FileSystemOverview/FileSystemOverview.html)
 //! - Window's [Known Folder Locations](https://docs.microsoft.com/en-us/windows/win32/shell/knownfolderid)
 //! - the "Unix Single-folder Convention" i.e. everything in `~/.myapp`
+//! - the [Flatpak](https://docs.flatpak.org/en/latest/conventions.html#xdg-base-directories) sandbox, which builds on the XDG base directories
+//! - the [Snap](https://snapcraft.io/docs/environment-variables) confinement, which builds on the XDG base directories too
+//!
+//! There is also an `InMemory` strategy, which never touches the OS and roots everything at a folder of your choice. It is used on `wasm32-unknown-unknown`, where there is no home directory.
 //!
 //! # Strategies
 //! If you want to get started quickly, you can use the following convenience functions that use the default strategies (as determined arbitrarily by yours truly) or the native strategies for each OS.
@@ -16,6 +20,8 @@
 //! If you just want to get the path to a configuration, data, cache or another directory, you can use the `choose_base_strategy` function.
 //!
 //! ```
+//! # #[cfg(feature = "std")]
+//! # {
 //! use etcetera::{choose_base_strategy, BaseStrategy};
 //!
 //! let strategy = choose_base_strategy().unwrap();
@@ -25,6 +31,9 @@
 //! let cache_dir = strategy.cache_dir();
 //! let state_dir = strategy.state_dir();
 //! let runtime_dir = strategy.runtime_dir();
+//! let log_dir = strategy.log_dir();
+//! let bin_dir = strategy.bin_dir();
+//! # }
 //! ```
 //!
 //! ## AppStrategy
@@ -37,12 +46,15 @@
 //! - Windows strategy would place these in `~\AppData\Roaming\Acme Corp\Frobnicator Plus`.
 //!
 //! ```
+//! # #[cfg(feature = "std")]
+//! # {
 //! use etcetera::{choose_app_strategy, AppStrategy, AppStrategyArgs};
 //!
 //! let strategy = choose_app_strategy(AppStrategyArgs {
 //!     top_level_domain: "org".to_string(),
 //!     author: "Acme Corp".to_string(),
 //!     app_name: "Frobnicator Plus".to_string(),
+//!     ..Default::default()
 //! }).unwrap();
 //!
 //! let config_dir = strategy.config_dir();
@@ -50,6 +62,9 @@
 //! let cache_dir = strategy.cache_dir();
 //! let state_dir = strategy.state_dir();
 //! let runtime_dir = strategy.runtime_dir();
+//! let log_dir = strategy.log_dir();
+//! let bin_dir = strategy.bin_dir();
+//! # }
 //! ```
 //!
 //! ## Native Strategy
@@ -60,6 +75,28 @@
 //! If you're developing a GUI application, you might want to use the "Standard directories" on macOS by using `choose_native_strategy()` instead.
 //! Note that if your application expects the user to modify the configuration files, you should still prefer the `XDG` strategy on macOS.
 //!
+//! Both are also re-exported at the crate root, as `choose_native_base_strategy()` & `choose_native_app_strategy()`.
+//! The strategy is selected at compile time, so these return the concrete strategy type for the current OS rather than a trait object.
+//! If you need to pick a strategy at runtime instead, [`AnyAppStrategy`](app_strategy/enum.AnyAppStrategy.html) can hold any of them, and `app_strategy::choose_app_strategy_enum()` & `app_strategy::choose_native_strategy_enum()` return one. [`AnyBaseStrategy`](base_strategy/enum.AnyBaseStrategy.html) & `base_strategy::choose_base_strategy_enum()` do the same for base strategies. Unlike their counterparts above, these functions honour the `ETCETERA_STRATEGY` environment variable, which forces a specific strategy, e.g. `ETCETERA_STRATEGY=windows` to test the Windows layout on Linux. To let your users choose with e.g. a command line flag instead, parse a [`StrategyKind`](enum.StrategyKind.html) and create the strategy from it.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use etcetera::{choose_native_app_strategy, choose_native_base_strategy, AppStrategy, AppStrategyArgs, BaseStrategy};
+//!
+//! let base_strategy = choose_native_base_strategy().unwrap();
+//! let app_strategy = choose_native_app_strategy(AppStrategyArgs {
+//!     top_level_domain: "org".to_string(),
+//!     author: "Acme Corp".to_string(),
+//!     app_name: "Frobnicator Plus".to_string(),
+//!     ..Default::default()
+//! }).unwrap();
+//!
+//! // On macOS, this is `~/Library/Application Support/org.acme-corp.Frobnicator-Plus`.
+//! assert!(app_strategy.data_dir().starts_with(base_strategy.data_dir()));
+//! # }
+//! ```
+//!
 //! ## Custom Conventions
 //!
 //! You aren’t limited to the built-in conventions – you can implement the relevant traits yourself. Please consider contributing these back, as the more preset conventions there are, the better.
@@ -68,27 +105,34 @@
 //! Say you were a hardened Unix veteran, and didn’t want to have any of this XDG nonsense, clutter in the home directory be damned! Instead of using `choose_app_strategy` or `choose_base_strategy`, you can pick a strategy yourself. Here’s an example using the [`Unix`](app_strategy/struct.Unix.html) strategy – see its documentation to see what kind of folder structures it produces:
 //!
 //! ```
+//! # #[cfg(feature = "unix")]
+//! # {
 //! use etcetera::{app_strategy, AppStrategy, AppStrategyArgs};
 //!
 //! let strategy = app_strategy::Unix::new(AppStrategyArgs {
 //!     top_level_domain: "com".to_string(),
 //!     author: "Hardened Unix Veteran Who Likes Short Command Names".to_string(),
 //!     app_name: "wry".to_string(),
+//!     ..Default::default()
 //! }).unwrap();
 //!
 //! let config_dir = strategy.config_dir(); // produces ~/.wry/
 //! // et cetera.
+//! # }
 //! ```
 //!
 //! Oftentimes the location of a configuration, data or cache directory is needed solely to create a path that starts inside it. For this purpose, [`AppStrategy`](app_strategy/trait.AppStrategy.html) implements a couple of convenience methods for you:
 //!
 //! ```
+//! # #[cfg(feature = "std")]
+//! # {
 //! use etcetera::{choose_app_strategy, AppStrategy, AppStrategyArgs};
 //!
 //! let strategy = choose_app_strategy(AppStrategyArgs {
 //!     top_level_domain: "org".to_string(),
 //!     author: "Acme Corp".to_string(),
 //!     app_name: "Frobnicator".to_string(),
+//!     ..Default::default()
 //! }).unwrap();
 //!
 //! // Path to configuration directory.
@@ -98,29 +142,852 @@
 //! let config_file = strategy.in_config_dir("config.toml");
 //!
 //! assert_eq!(config_dir.join("config.toml"), config_file);
+//!
+//! // The same is available for the other directories.
+//! assert_eq!(strategy.data_dir().join("db.sqlite"), strategy.in_data_dir("db.sqlite"));
+//! assert_eq!(strategy.cache_dir().join("thumbnails"), strategy.in_cache_dir("thumbnails"));
+//! assert_eq!(strategy.bin_dir().join("frobnicate"), strategy.in_bin_dir("frobnicate"));
+//! assert_eq!(
+//!     strategy.state_dir().map(|dir| dir.join("history")),
+//!     strategy.in_state_dir("history")
+//! );
+//! # }
+//! ```
+//!
+//! # Thread Safety
+//! Every strategy, wrapper & helper type of this crate is `Send + Sync`, as long as the types it is generic over are, e.g. a custom [`EnvProvider`](env/trait.EnvProvider.html). This is checked at compile time, so it won’t silently change.
+//!
+//! The traits themselves don’t require `Send + Sync`, so that your own strategies can still use `Rc` or `RefCell`. Spell out the bounds to share a trait object across threads or async tasks:
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use etcetera::{choose_base_strategy, BaseStrategy};
+//! use std::sync::Arc;
+//!
+//! let strategy: Arc<dyn BaseStrategy + Send + Sync> = Arc::new(choose_base_strategy().unwrap());
+//!
+//! let config_dir = std::thread::spawn({
+//!     let strategy = Arc::clone(&strategy);
+//!     move || strategy.config_dir()
+//! })
+//! .join()
+//! .unwrap();
+//!
+//! assert_eq!(config_dir, strategy.config_dir());
+//! # }
 //! ```
+//!
+//! # Extensibility
+//! The public enums of this crate, such as [`StrategyKind`](enum.StrategyKind.html), [`DirKind`](enum.DirKind.html) & [`CreationError`](enum.CreationError.html), are `#[non_exhaustive]`, as new strategies, directories & failure modes are added over time. Adding a variant is therefore not a breaking change, but matching on them needs a wildcard arm. [`HomeDirError`](struct.HomeDirError.html) is opaque for the same reason: inspect it through `Display` & [`source`](std::error::Error::source).
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use etcetera::app_strategy::{choose_app_strategy, AppStrategyArgs};
+//! use etcetera::CreationError;
+//!
+//! let err = choose_app_strategy(AppStrategyArgs {
+//!     app_name: "../frobnicator".to_string(),
+//!     ..Default::default()
+//! })
+//! .unwrap_err();
+//!
+//! let message = match err {
+//!     CreationError::HomeDir(_) => "no home directory".to_string(),
+//!     CreationError::InvalidAppName { reason, .. } => format!("bad name: {reason}"),
+//!     _ => "something else".to_string(),
+//! };
+//! assert!(message.starts_with("bad name: "));
+//! # }
+//! ```
+//!
+//! # Cargo Features
+//! - `std` (enabled by default): everything but the [`spec`](spec/index.html) module, which only needs `alloc`.
+//! - `xdg`, `windows`, `apple` & `unix` (all enabled by default): the strategies of each layout, i.e. [`Xdg`](base_strategy/struct.Xdg.html), [`Flatpak`](base_strategy/struct.Flatpak.html) & [`Snap`](base_strategy/struct.Snap.html) for `xdg`, the `Windows` & `Apple` strategies for the next two, and the [`Unix`](app_strategy/struct.Unix.html) app strategy for `unix`. Disable the default features & enable only the ones you need to compile less code, e.g. just `xdg` for a Linux-only server. The other strategies, the wrappers & the traits are always available.
+//!
+//!   The `choose_*` functions only pick from the enabled strategies: if the one for the current OS is disabled, they fall back to `Xdg`, then to an [`InMemory`](base_strategy/struct.InMemory.html) strategy rooted at the home directory. On macOS, `choose_base_strategy` & `choose_app_strategy` return `Apple` if only `apple` is enabled. `ETCETERA_STRATEGY` & [`StrategyKind`](enum.StrategyKind.html) only know about the enabled layouts. The platform-specific dependencies are only ever used on their own platform, so disabling features doesn’t change them.
+//! - `async`: adds [`AsyncDirs`](struct.AsyncDirs.html), which creates your application’s directories through [`tokio::fs`](https://docs.rs/tokio/latest/tokio/fs/index.html) instead of blocking the executor. It isn’t available on `wasm32-unknown-unknown`.
+//! - `camino`: adds `*_utf8` accessors to [`BaseStrategy`](base_strategy/trait.BaseStrategy.html) & [`AppStrategy`](app_strategy/trait.AppStrategy.html), which return [`camino`](https://docs.rs/camino) paths, or `None` if a path isn’t valid UTF-8.
+//! - `proc-environ`: adds [`Xdg::from_proc_environ`](base_strategy/struct.Xdg.html#method.from_proc_environ) & [`ProcEnv`](env/struct.ProcEnv.html), which read the environment of another process from `/proc` on Linux.
+//! - `serde`: implements `Serialize` & `Deserialize` for [`AppStrategyArgs`](app_strategy/struct.AppStrategyArgs.html) & [`ResolvedDirs`](struct.ResolvedDirs.html).
+//! - `test-util`: adds [`TempStrategy`](struct.TempStrategy.html), which keeps everything in a temporary directory that is removed again once your test is done.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs, rust_2018_idioms, missing_debug_implementations)]
 
+extern crate alloc;
+
+// The `*_utf8` accessors are the same for both traits.
+#[cfg(feature = "camino")]
+macro_rules! utf8_methods {
+    ($($name: ident => $method: ident),* $(,)?) => {
+        /// Gets [`home_dir`](#tymethod.home_dir) as a UTF-8 path, or `None` if it isn’t valid UTF-8.
+        fn home_dir_utf8(&self) -> Option<&camino::Utf8Path> {
+            camino::Utf8Path::from_path(self.home_dir())
+        }
+
+        $(
+            #[doc = concat!("Gets [`", stringify!($method), "`](#tymethod.", stringify!($method), ") as a UTF-8 path, or `None` if there is no such directory or it isn’t valid UTF-8.")]
+            fn $name(&self) -> Option<camino::Utf8PathBuf> {
+                // This accepts both `PathBuf` & `Option<PathBuf>`.
+                let path: Option<std::path::PathBuf> = self.$method().into();
+                path.and_then(|path| camino::Utf8PathBuf::from_path_buf(path).ok())
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "std")]
 pub mod app_strategy;
+#[cfg(all(
+    feature = "async",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+mod async_dirs;
+#[cfg(feature = "std")]
 pub mod base_strategy;
+#[cfg(feature = "std")]
+mod cached;
+#[cfg(feature = "std")]
+pub mod compat;
+#[cfg(feature = "std")]
+mod dir_kind;
+#[cfg(feature = "std")]
+mod dir_path;
+#[cfg(feature = "std")]
+mod dir_source;
+#[cfg(feature = "std")]
+pub mod env;
+#[cfg(feature = "std")]
+pub mod fs;
+#[cfg(feature = "std")]
+mod overrides;
+#[cfg(feature = "std")]
+mod prefer_existing;
+#[cfg(feature = "std")]
+mod profile;
+#[cfg(feature = "std")]
+mod resolved_dirs;
+pub mod spec;
+#[cfg(feature = "std")]
+mod strategy_kind;
+#[cfg(feature = "test-util")]
+mod temp_strategy;
+#[cfg(feature = "std")]
+mod versioned;
+
+#[cfg(feature = "std")]
+pub use app_strategy::{
+    choose_app_strategy, choose_native_strategy as choose_native_app_strategy, AppStrategy,
+    AppStrategyArgs,
+};
+#[cfg(all(
+    feature = "async",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub use async_dirs::AsyncDirs;
+#[cfg(feature = "std")]
+pub use base_strategy::{
+    choose_base_strategy, choose_native_strategy as choose_native_base_strategy, BaseStrategy,
+};
+#[cfg(feature = "std")]
+pub use cached::Cached;
+#[cfg(feature = "std")]
+pub use dir_kind::{ConfigScope, DirClassification, DirKind};
+#[cfg(feature = "std")]
+pub use dir_path::DirPath;
+#[cfg(feature = "std")]
+pub use dir_source::DirSource;
+#[cfg(feature = "std")]
+pub use overrides::Override;
+#[cfg(feature = "std")]
+pub use prefer_existing::PreferExisting;
+#[cfg(feature = "std")]
+pub use profile::Profile;
+#[cfg(feature = "std")]
+pub use resolved_dirs::{describe, ResolvedDirs};
+#[cfg(feature = "std")]
+pub use strategy_kind::{ParseStrategyKindError, StrategyKind};
+#[cfg(feature = "test-util")]
+pub use temp_strategy::TempStrategy;
+#[cfg(feature = "std")]
+pub use versioned::Versioned;
 
-pub use app_strategy::{choose_app_strategy, AppStrategy, AppStrategyArgs};
-pub use base_strategy::{choose_base_strategy, BaseStrategy};
+// Fails to compile if any of the public types stops being `Send + Sync`. See the “Thread Safety” section above.
+#[cfg(feature = "std")]
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
 
-/// A convenience function that wraps the [`home_dir`](https://docs.rs/home/0.5.4/home/fn.home_dir.html) function from the [home](https://docs.rs/home) crate.
+    assert_send_sync::<base_strategy::AnyBaseStrategy>();
+    #[cfg(feature = "apple")]
+    assert_send_sync::<base_strategy::Apple>();
+    assert_send_sync::<base_strategy::Custom>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<base_strategy::Flatpak>();
+    assert_send_sync::<base_strategy::InMemory>();
+    assert_send_sync::<base_strategy::Portable>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<base_strategy::Snap>();
+    #[cfg(feature = "windows")]
+    assert_send_sync::<base_strategy::Windows>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<base_strategy::Xdg>();
+    assert_send_sync::<Box<dyn BaseStrategy + Send + Sync>>();
+
+    assert_send_sync::<app_strategy::AnyAppStrategy>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<app_strategy::App<base_strategy::Xdg>>();
+    #[cfg(feature = "apple")]
+    assert_send_sync::<app_strategy::Apple>();
+    assert_send_sync::<app_strategy::InMemory>();
+    #[cfg(feature = "unix")]
+    assert_send_sync::<app_strategy::Unix>();
+    #[cfg(feature = "windows")]
+    assert_send_sync::<app_strategy::Windows>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<app_strategy::Xdg>();
+    assert_send_sync::<app_strategy::LockGuard>();
+    assert_send_sync::<AppStrategyArgs>();
+
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<Cached<base_strategy::Xdg>>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<Override<base_strategy::Xdg>>();
+    assert_send_sync::<PreferExisting<app_strategy::AnyAppStrategy>>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<Profile<app_strategy::Xdg>>();
+    #[cfg(feature = "xdg")]
+    assert_send_sync::<Versioned<app_strategy::Xdg>>();
+    assert_send_sync::<compat::ProjectDirs>();
+    assert_send_sync::<env::DotEnv>();
+    assert_send_sync::<fs::MemoryFs>();
+    assert_send_sync::<fs::DryRunFs<fs::StdFs>>();
+    assert_send_sync::<ResolvedDirs>();
+    assert_send_sync::<DirPath>();
+    assert_send_sync::<ConfigScope>();
+    assert_send_sync::<StrategyKind>();
+    assert_send_sync::<ParseStrategyKindError>();
+    assert_send_sync::<HomeDirError>();
+    assert_send_sync::<CreationError>();
+};
+
+/// Returns the current OS’s native [`BaseStrategy`](base_strategy/trait.BaseStrategy.html), i.e. the same strategy as [`choose_native_base_strategy`](base_strategy/fn.choose_native_strategy.html), without you having to name its type.
+///
+/// The strategy is picked at compile time, so this is a single concrete type on each target, and every call through it is statically dispatched: unlike with a `Box<dyn BaseStrategy>`, nothing is boxed. Use [`choose_base_strategy_enum`](base_strategy/fn.choose_base_strategy_enum.html) if you need to pick a strategy at runtime instead.
+///
+/// ```
+/// use etcetera::BaseStrategy;
+///
+/// let base_strategy = etcetera::native_base_strategy().unwrap();
+///
+/// assert_eq!(
+///     base_strategy.config_dir(),
+///     etcetera::choose_native_base_strategy().unwrap().config_dir()
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn native_base_strategy() -> Result<impl BaseStrategy, HomeDirError> {
+    base_strategy::choose_native_strategy()
+}
+
+/// Gets the home directory of the current user, which all the strategies are based on.
+///
+/// On Unix, this reads `HOME` & falls back to the password database, as described below. On Windows, it reads the environment variables described below before falling back to the [`home_dir`](https://docs.rs/home/0.5.4/home/fn.home_dir.html) function from the [home](https://docs.rs/home) crate, which is used on all other platforms.
+///
+/// On `wasm32-unknown-unknown`, there is no home directory, so this always returns an error.
+///
+/// On Unix, if `HOME` is unset or empty, e.g. in cron jobs or daemons, the home directory of the effective user is looked up in the password database instead, like shells do. An empty `HOME` is never returned as the home directory, as every path derived from it would then be relative to the working directory.
+///
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// std::env::set_var("HOME", "");
+///
+/// match etcetera::home_dir() {
+///     Ok(home_dir) => assert!(home_dir.is_absolute()),
+///     // The current user has no entry in the password database.
+///     Err(err) => assert_eq!(err.to_string(), "could not locate home directory, as `HOME` is empty"),
+/// }
+/// # }
+/// ```
+///
+/// On Windows, `HOME` is used as well if it is set to an absolute path, as Git Bash, MSYS2 & Cygwin users expect their shell’s home directory to be respected. Otherwise, `USERPROFILE` is used, then `HOMEDRIVE` joined with `HOMEPATH`, and finally the profile folder Windows itself reports.
+///
+/// ```
+/// # #[cfg(windows)]
+/// # {
+/// use std::path::Path;
+///
+/// std::env::set_var("HOME", "D:\\msys64\\home\\me");
+/// std::env::set_var("USERPROFILE", "C:\\Users\\me");
+/// std::env::set_var("HOMEDRIVE", "E:");
+/// std::env::set_var("HOMEPATH", "\\Users\\me");
+/// assert_eq!(etcetera::home_dir().unwrap(), Path::new("D:\\msys64\\home\\me"));
+///
+/// // A relative `HOME` is ignored.
+/// std::env::set_var("HOME", "/home/me");
+/// assert_eq!(etcetera::home_dir().unwrap(), Path::new("C:\\Users\\me"));
+///
+/// std::env::remove_var("HOME");
+/// std::env::remove_var("USERPROFILE");
+/// assert_eq!(etcetera::home_dir().unwrap(), Path::new("E:\\Users\\me"));
+///
+/// std::env::remove_var("HOMEDRIVE");
+/// assert!(etcetera::home_dir().unwrap().is_absolute());
+/// # }
+/// ```
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
 pub fn home_dir() -> Result<std::path::PathBuf, HomeDirError> {
-    home::home_dir().ok_or(HomeDirError)
+    // Older versions of std return an empty `HOME` as-is, so don’t rely on them for the fallback.
+    #[cfg(unix)]
+    return match std::env::var_os("HOME") {
+        Some(home) if !home.is_empty() => Ok(std::path::PathBuf::from(home)),
+        home => passwd_home_dir(effective_uid()).ok_or_else(|| HomeDirError {
+            kind: match home {
+                Some(_) => HomeDirErrorKind::Empty { env_var: "HOME" },
+                None => HomeDirErrorKind::NotFound {
+                    env_var: "HOME",
+                    source: std::env::var("HOME").err(),
+                },
+            },
+        }),
+    };
+
+    #[cfg(windows)]
+    if let Some(home_dir) = windows_home_dir() {
+        return Ok(home_dir);
+    }
+
+    #[cfg(not(unix))]
+    home::home_dir().ok_or_else(|| {
+        // `home` doesn’t tell us why it failed, but the variable it looks at first is the most likely culprit.
+        let env_var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+        HomeDirError {
+            kind: HomeDirErrorKind::NotFound {
+                env_var,
+                source: std::env::var(env_var).err(),
+            },
+        }
+    })
+}
+
+// Reads the home directory from the environment, in the order described by `home_dir`.
+#[cfg(all(feature = "std", windows))]
+fn windows_home_dir() -> Option<std::path::PathBuf> {
+    use std::path::PathBuf;
+
+    let var = |key| std::env::var_os(key).filter(|value| !value.is_empty());
+
+    // Relative values, e.g. the `/c/Users/me` of an MSYS2 shell which didn’t convert it, can’t be used.
+    if let Some(home) = var("HOME").map(PathBuf::from) {
+        if home.is_absolute() {
+            return Some(home);
+        }
+    }
+
+    if let Some(profile) = var("USERPROFILE") {
+        return Some(profile.into());
+    }
+
+    let mut home = var("HOMEDRIVE")?;
+    home.push(var("HOMEPATH")?);
+    Some(home.into())
+}
+
+// Looks up the home directory of the user `uid`, for strategies acting on behalf of another user.
+#[cfg(all(feature = "xdg", unix))]
+pub(crate) fn user_home_dir(uid: u32) -> Result<std::path::PathBuf, HomeDirError> {
+    passwd_home_dir(uid).ok_or(HomeDirError {
+        kind: HomeDirErrorKind::UnknownUser(uid),
+    })
 }
 
-/// This error occurs when the home directory cannot be located.
+// Looks up the home directory of the user `uid` with `getpwuid_r`.
+#[cfg(all(feature = "std", unix))]
+fn passwd_home_dir(uid: libc::uid_t) -> Option<std::path::PathBuf> {
+    use std::ffi::{CStr, OsStr};
+    use std::os::unix::ffi::OsStrExt;
+
+    // This is only a hint, and some platforms don’t provide it at all.
+    // SAFETY: `sysconf` has no preconditions.
+    let mut buf_len = match unsafe { libc::sysconf(libc::_SC_GETPW_R_SIZE_MAX) } {
+        len if len > 0 => len as usize,
+        _ => 1024,
+    };
+
+    loop {
+        let mut buf = vec![0; buf_len];
+        // SAFETY: `passwd` only contains integers & pointers, for which all zeroes are valid.
+        let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+
+        // SAFETY: Every pointer is valid, and `buf_len` is the length of `buf`.
+        let code =
+            unsafe { libc::getpwuid_r(uid, &mut passwd, buf.as_mut_ptr(), buf_len, &mut result) };
+
+        match code {
+            0 if !result.is_null() && !passwd.pw_dir.is_null() => {
+                // SAFETY: `getpwuid_r` succeeded, so `pw_dir` is a NUL-terminated string inside `buf`.
+                let dir = unsafe { CStr::from_ptr(passwd.pw_dir) }.to_bytes();
+                return (!dir.is_empty()).then(|| OsStr::from_bytes(dir).into());
+            }
+            // The buffer is too small, but give up eventually rather than exhaust the memory.
+            libc::ERANGE if buf_len < 1 << 20 => buf_len *= 2,
+            _ => return None,
+        }
+    }
+}
+
+/// Gets the home directory of the current user, which all the strategies are based on.
+///
+/// On `wasm32-unknown-unknown`, there is no home directory, so this always returns an error.
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+pub fn home_dir() -> Result<std::path::PathBuf, HomeDirError> {
+    Err(HomeDirError {
+        kind: HomeDirErrorKind::Unsupported,
+    })
+}
+
+/// Gets the effective user ID of the current process.
+///
+/// Directories created in locations shared by all users, such as the fallback of [`runtime_dir_or_fallback`](base_strategy/trait.BaseStrategy.html#method.runtime_dir_or_fallback), are namespaced by this ID.
+///
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// use std::os::unix::fs::MetadataExt;
+///
+/// let file = std::env::temp_dir().join(format!("etcetera-effective-uid-{}", std::process::id()));
+/// std::fs::write(&file, "").unwrap();
+///
+/// assert_eq!(file.metadata().unwrap().uid(), etcetera::effective_uid());
+///
+/// std::fs::remove_file(&file).unwrap();
+/// # }
+/// ```
+#[cfg(all(feature = "std", unix))]
+pub fn effective_uid() -> u32 {
+    // SAFETY: `geteuid` has no preconditions & always succeeds.
+    unsafe { libc::geteuid() }
+}
+
+/// Gets the directory containing the running executable, with symlinks resolved, so that a symlink to your executable, e.g. in `~/.local/bin`, still leads to the files you ship next to it.
+/// This is the root of the [`Portable`](base_strategy/struct.Portable.html) strategy. On Windows, resolving symlinks turns it into a verbatim path, i.e. one starting with `\\?\`, like [`std::fs::canonicalize`] does.
+///
+/// ```
+/// let exe_dir = etcetera::exe_dir().unwrap();
+///
+/// assert!(exe_dir.is_absolute());
+/// assert_eq!(
+///     exe_dir,
+///     std::fs::canonicalize(std::env::current_exe().unwrap()).unwrap().parent().unwrap()
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn exe_dir() -> std::io::Result<std::path::PathBuf> {
+    let exe = std::fs::canonicalize(std::env::current_exe()?)?;
+    match exe.parent() {
+        Some(exe_dir) => Ok(exe_dir.to_path_buf()),
+        None => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "the current executable has no parent directory",
+        )),
+    }
+}
+
+/// Gets the directory containing the resources you ship with your executable.
+///
+/// If the executable is inside a macOS application bundle, i.e. at `Frobnicator.app/Contents/MacOS/frobnicator`, this is the bundle’s `Frobnicator.app/Contents/Resources` directory. Otherwise, this is the same as [`exe_dir`](fn.exe_dir.html).
+///
+/// ```
+/// // Tests aren’t run from inside an application bundle.
+/// assert_eq!(etcetera::resources_dir().unwrap(), etcetera::exe_dir().unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn resources_dir() -> std::io::Result<std::path::PathBuf> {
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    let exe_dir = exe_dir()?;
+    let contents_dir = exe_dir.parent();
+    let is_bundle = exe_dir.file_name() == Some(OsStr::new("MacOS"))
+        && contents_dir.and_then(Path::file_name) == Some(OsStr::new("Contents"))
+        && contents_dir
+            .and_then(Path::parent)
+            .and_then(Path::extension)
+            == Some(OsStr::new("app"));
+
+    if is_bundle {
+        Ok(exe_dir.with_file_name("Resources"))
+    } else {
+        Ok(exe_dir)
+    }
+}
+
+/// Resolves symlinks in `path` like [`std::fs::canonicalize`], except that the path doesn’t need to exist: the longest existing prefix is canonicalized, and the rest is appended as-is.
+/// This makes comparing directories reliable even when some of them, e.g. `~/.config`, are symlinks.
+///
+/// ```
+/// let root = std::env::temp_dir().join(format!("etcetera-canonicalize-existing-{}", std::process::id()));
+/// std::fs::create_dir_all(root.join("real")).unwrap();
+///
+/// let canonical_root = std::fs::canonicalize(&root).unwrap();
+/// assert_eq!(
+///     etcetera::canonicalize_existing(root.join("real/missing/file")).unwrap(),
+///     canonical_root.join("real/missing/file")
+/// );
+///
+/// #[cfg(unix)]
+/// {
+///     let _ = std::fs::remove_file(root.join("link"));
+///     std::os::unix::fs::symlink(root.join("real"), root.join("link")).unwrap();
+///     assert_eq!(
+///         etcetera::canonicalize_existing(root.join("link/missing")).unwrap(),
+///         canonical_root.join("real/missing")
+///     );
+/// }
+///
+/// std::fs::remove_dir_all(&root).unwrap();
+/// ```
+#[cfg(feature = "std")]
+pub fn canonicalize_existing<P: AsRef<std::path::Path>>(
+    path: P,
+) -> std::io::Result<std::path::PathBuf> {
+    let mut existing = path.as_ref();
+    let mut tail = Vec::new();
+
+    loop {
+        match std::fs::canonicalize(existing) {
+            Ok(mut canonical) => {
+                canonical.extend(tail.iter().rev());
+                return Ok(canonical);
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                match (existing.parent(), existing.file_name()) {
+                    (Some(parent), Some(file_name)) => {
+                        tail.push(file_name);
+                        existing = parent;
+                    }
+                    _ => return Err(err),
+                }
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Returns the first of `candidates` which can be written to, creating it (and any missing parents) if need be, e.g. to find a fallback when the usual location is read-only on a locked-down system.
+///
+/// Each candidate is probed by creating it, then creating & removing an empty file inside it, as permissions alone don’t tell whether a directory is writable, e.g. on a read-only mount. If no candidate is writable, the error lists every attempt, and has the kind of the last one’s error.
+///
+/// ```
+/// let root = std::env::temp_dir().join(format!("etcetera-first-writable-dir-{}", std::process::id()));
+/// std::fs::create_dir_all(&root).unwrap();
+///
+/// // Nothing can be created below a file.
+/// std::fs::write(root.join("file"), "").unwrap();
+///
+/// assert_eq!(
+///     etcetera::first_writable_dir([root.join("file/config"), root.join("config")]).unwrap(),
+///     root.join("config")
+/// );
+/// assert!(etcetera::first_writable_dir([root.join("file/config")]).is_err());
+///
+/// std::fs::remove_dir_all(&root).unwrap();
+/// ```
+#[cfg(feature = "std")]
+pub fn first_writable_dir<I: IntoIterator<Item = std::path::PathBuf>>(
+    candidates: I,
+) -> std::io::Result<std::path::PathBuf> {
+    use std::io;
+
+    fn probe(dir: &std::path::Path) -> io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        // The process ID keeps concurrent probes from tripping over each other.
+        let probe = dir.join(format!(".etcetera-probe-{}", std::process::id()));
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)?;
+        std::fs::remove_file(probe)
+    }
+
+    let mut attempts = Vec::new();
+    let mut kind = io::ErrorKind::NotFound;
+
+    for dir in candidates {
+        match probe(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) => {
+                kind = err.kind();
+                attempts.push(format!("{}: {err}", dir.display()));
+            }
+        }
+    }
+
+    let message = if attempts.is_empty() {
+        "there are no candidate directories".to_string()
+    } else {
+        format!("no directory is writable ({})", attempts.join("; "))
+    };
+    Err(io::Error::new(kind, message))
+}
+
+// Lists the files directly inside `dir` whose extension is `extension`, ignoring case unless `case_sensitive` is true.
+#[cfg(feature = "std")]
+pub(crate) fn list_files(
+    dir: &std::path::Path,
+    extension: &str,
+    case_sensitive: bool,
+) -> std::io::Result<Vec<std::path::PathBuf>> {
+    let extension = extension.strip_prefix('.').unwrap_or(extension);
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let matches = path.extension().is_some_and(|ext| {
+            ext.to_str().is_some_and(|ext| {
+                if case_sensitive {
+                    ext == extension
+                } else {
+                    ext.eq_ignore_ascii_case(extension)
+                }
+            })
+        });
+        // `is_file` follows symlinks, so links to files are listed too.
+        if matches && path.is_file() {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Abbreviates `path` for display by replacing the `home` directory it lies in with `~`, e.g. `~/.config/frobnicator-plus` instead of `/home/user/.config/frobnicator-plus`. `home` itself becomes `~`, and paths outside of it are returned as-is.
+///
+/// Like [`is_within_home`](base_strategy/trait.BaseStrategy.html#method.is_within_home), the comparison is lexical, and paths containing `..` are never abbreviated. The result is meant for humans, so non-UTF-8 paths are converted lossily.
+///
+/// ```
+/// use std::path::Path;
+///
+/// let (home, sep) = if cfg!(windows) { ("C:\\Users\\user", "\\") } else { ("/home/user", "/") };
+/// let home = Path::new(home);
+///
+/// assert_eq!(
+///     etcetera::tilde_abbreviate(&home.join(".config").join("frobnicator-plus"), home),
+///     format!("~{sep}.config{sep}frobnicator-plus")
+/// );
+/// assert_eq!(etcetera::tilde_abbreviate(home, home), "~");
+///
+/// let outside = home.parent().unwrap().join("other");
+/// assert_eq!(etcetera::tilde_abbreviate(&outside, home), outside.display().to_string());
+/// ```
+#[cfg(feature = "std")]
+pub fn tilde_abbreviate(path: &std::path::Path, home: &std::path::Path) -> String {
+    if !is_within(path, home) {
+        return path.display().to_string();
+    }
+
+    match path.strip_prefix(home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => format!("~{}{}", std::path::MAIN_SEPARATOR, rest.display()),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+// Lexically checks whether `path` is inside `dir`. `..` could escape `dir`, so such paths are never inside it.
+#[cfg(feature = "std")]
+pub(crate) fn is_within(path: &std::path::Path, dir: &std::path::Path) -> bool {
+    path.starts_with(dir)
+        && !path
+            .components()
+            .any(|component| component == std::path::Component::ParentDir)
+}
+
+// Home directories provided by the user must be absolute, otherwise every derived path would be relative to the working directory.
+#[cfg(feature = "std")]
+pub(crate) fn validate_home_dir(
+    home_dir: std::path::PathBuf,
+) -> Result<std::path::PathBuf, HomeDirError> {
+    if home_dir.is_absolute() {
+        Ok(home_dir)
+    } else {
+        Err(HomeDirError {
+            kind: HomeDirErrorKind::NotAbsolute(home_dir),
+        })
+    }
+}
+
+/// This error occurs when the home directory cannot be located, or when the home directory provided to a strategy isn’t absolute.
+///
+/// If the home directory could not be located because of an environment variable, the [`source`](std::error::Error::source) of this error tells you what was wrong with it.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::base_strategy::Xdg;
+/// use std::error::Error;
+///
+/// let err = Xdg::with_home_dir("relative/home".into()).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "the home directory \"relative/home\" is not an absolute path"
+/// );
+/// assert!(err.source().is_none());
+/// # }
+/// ```
+#[cfg(feature = "std")]
 #[derive(Debug)]
-pub struct HomeDirError;
+pub struct HomeDirError {
+    kind: HomeDirErrorKind,
+}
 
+#[cfg(feature = "std")]
+#[derive(Debug)]
+enum HomeDirErrorKind {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    NotFound {
+        env_var: &'static str,
+        source: Option<std::env::VarError>,
+    },
+    #[cfg(unix)]
+    Empty {
+        env_var: &'static str,
+    },
+    #[cfg(all(feature = "xdg", unix))]
+    UnknownUser(u32),
+    NotAbsolute(std::path::PathBuf),
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    Unsupported,
+}
+
+#[cfg(feature = "std")]
 impl std::fmt::Display for HomeDirError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "could not locate home directory")
+        match &self.kind {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            HomeDirErrorKind::NotFound { env_var, .. } => {
+                write!(f, "could not locate home directory from `{env_var}`")
+            }
+            #[cfg(unix)]
+            HomeDirErrorKind::Empty { env_var } => {
+                write!(
+                    f,
+                    "could not locate home directory, as `{env_var}` is empty"
+                )
+            }
+            #[cfg(all(feature = "xdg", unix))]
+            HomeDirErrorKind::UnknownUser(uid) => {
+                write!(
+                    f,
+                    "could not locate home directory of the user with ID {uid}"
+                )
+            }
+            HomeDirErrorKind::NotAbsolute(home_dir) => {
+                write!(f, "the home directory {home_dir:?} is not an absolute path")
+            }
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            HomeDirErrorKind::Unsupported => {
+                write!(
+                    f,
+                    "could not locate home directory, as this platform has none"
+                )
+            }
+        }
     }
 }
 
-impl std::error::Error for HomeDirError {}
+#[cfg(feature = "std")]
+impl std::error::Error for HomeDirError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            HomeDirErrorKind::NotFound {
+                source: Some(source),
+                ..
+            } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// This error occurs when a strategy cannot be created, or when a name passed to it cannot be used as a folder name.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CreationError {
+    /// The home directory could not be located.
+    HomeDir(HomeDirError),
+    /// The application’s name, or its explicit bundle identifier, cannot be used as a folder name.
+    InvalidAppName {
+        /// The offending name.
+        app_name: String,
+        /// Why the name was rejected.
+        reason: &'static str,
+    },
+    /// The version given to [`Versioned`](struct.Versioned.html) cannot be used as a folder name.
+    InvalidVersion {
+        /// The offending version.
+        version: String,
+        /// Why the version was rejected.
+        reason: &'static str,
+    },
+    /// The App Group identifier given to [`group_container_dir`](base_strategy/struct.Apple.html#method.group_container_dir) cannot be used as a folder name.
+    #[cfg(feature = "apple")]
+    InvalidGroupId {
+        /// The offending identifier.
+        group_id: String,
+        /// Why the identifier was rejected.
+        reason: &'static str,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for CreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HomeDir(err) => err.fmt(f),
+            Self::InvalidAppName { app_name, reason } => {
+                write!(f, "invalid application name {app_name:?}: {reason}")
+            }
+            Self::InvalidVersion { version, reason } => {
+                write!(f, "invalid version {version:?}: {reason}")
+            }
+            #[cfg(feature = "apple")]
+            Self::InvalidGroupId { group_id, reason } => {
+                write!(f, "invalid App Group identifier {group_id:?}: {reason}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CreationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            // The `Display` implementation is transparent, so skip straight to the cause.
+            Self::HomeDir(err) => err.source(),
+            Self::InvalidAppName { .. } | Self::InvalidVersion { .. } => None,
+            #[cfg(feature = "apple")]
+            Self::InvalidGroupId { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<HomeDirError> for CreationError {
+    fn from(err: HomeDirError) -> Self {
+        Self::HomeDir(err)
+    }
+}
@@ -0,0 +1,480 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::app_strategy::AppStrategy;
+use crate::base_strategy::BaseStrategy;
+use crate::DirSource;
+
+/// Wraps a [`BaseStrategy`](base_strategy/trait.BaseStrategy.html) or an [`AppStrategy`](app_strategy/trait.AppStrategy.html), and replaces some of its directories with explicit ones, e.g. from a `--config-dir` command-line flag.
+///
+/// As the wrapped strategy already prefers the environment variables to its defaults, the directories are resolved in this order: the explicit override, then your own environment variables if you [registered any](#method.with_config_env_vars), then the strategy’s environment variable, then the default. Each override is used as-is, so for an app strategy it is the application’s directory itself rather than a folder to append the application’s name to.
+///
+/// The [`data_dir`](#method.with_data_dir) override applies to the local data directory too, the [`config_dir`](#method.with_config_dir) one to the preference directory of app strategies, and the [`state_dir`](#method.with_state_dir) one to their secret directory, which becomes `secrets/` inside it. Every other directory comes from the wrapped strategy.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+/// use etcetera::{DirSource, Override};
+/// use std::path::{Path, PathBuf};
+///
+/// let config_path = if cfg!(windows) {
+///     "C:\\my_config_location\\"
+/// } else {
+///     "/my_config_location/"
+/// };
+///
+/// // This would usually come from a command-line flag.
+/// let config_dir_flag = Some(PathBuf::from(config_path));
+///
+/// let inner = Xdg::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// }).unwrap();
+/// let app_strategy = Override::new(inner.clone()).with_config_dir(config_dir_flag);
+///
+/// assert_eq!(app_strategy.config_dir(), Path::new(config_path));
+/// assert_eq!(app_strategy.in_config_dir("config.toml"), Path::new(config_path).join("config.toml"));
+/// assert_eq!(app_strategy.data_dir(), inner.data_dir());
+/// assert_eq!(app_strategy.config_dir_with_source().1, DirSource::Override);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Override<S> {
+    strategy: S,
+    config_dir: OverriddenDir,
+    data_dir: OverriddenDir,
+    cache_dir: OverriddenDir,
+    state_dir: OverriddenDir,
+    runtime_dir: OverriddenDir,
+    log_dir: OverriddenDir,
+    bin_dir: OverriddenDir,
+}
+
+impl<S> Override<S> {
+    /// Wraps `strategy` without overriding anything yet.
+    pub fn new(strategy: S) -> Self {
+        Self {
+            strategy,
+            config_dir: OverriddenDir::default(),
+            data_dir: OverriddenDir::default(),
+            cache_dir: OverriddenDir::default(),
+            state_dir: OverriddenDir::default(),
+            runtime_dir: OverriddenDir::default(),
+            log_dir: OverriddenDir::default(),
+            bin_dir: OverriddenDir::default(),
+        }
+    }
+
+    /// Overrides the configuration directory. Passing `None` keeps the wrapped strategy’s one.
+    pub fn with_config_dir(mut self, config_dir: impl Into<Option<PathBuf>>) -> Self {
+        self.config_dir.dir = config_dir.into();
+        self
+    }
+
+    /// Overrides the configuration directory with the first of the environment variables `env_vars` which is set to an absolute path, e.g. an application-specific `MYAPP_CONFIG`. If none of them is, the wrapped strategy’s directory is used, which may come from its own environment variables such as `XDG_CONFIG_HOME`.
+    ///
+    /// Like the other overrides, the variables name the application’s directory itself. The base directory variables read by this crate’s strategies, e.g. `XDG_CONFIG_HOME` or `APPDATA`, are skipped if you list them: they name a directory shared by every application, so your application’s files would end up directly inside it. The wrapped strategy already reads them, & appends your application’s name to them.
+    ///
+    /// An explicit [`with_config_dir`](#method.with_config_dir) takes precedence over the variables, regardless of the order the methods are called in. They are read whenever the directory is needed, through the wrapped strategy’s [`env_var_os`](app_strategy/trait.AppStrategy.html#method.env_var_os), so that they come from the same environment as its own, and replace those passed earlier.
+    ///
+    /// The same methods exist for the other directories, e.g. [`with_data_env_vars`](#method.with_data_env_vars).
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use etcetera::{DirSource, Override};
+    /// use std::collections::HashMap;
+    /// use std::path::Path;
+    ///
+    /// let config_path = if cfg!(windows) {
+    ///     "C:\\frobnicator\\config\\"
+    /// } else {
+    ///     "/frobnicator/config/"
+    /// };
+    ///
+    /// let args = AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let env_vars = ["FROBNICATOR_CONFIG", "FROBNICATOR_HOME"];
+    ///
+    /// let inner = Xdg::new_with_env(args.clone(), HashMap::<&str, &str>::new()).unwrap();
+    /// let app_strategy = Override::new(inner.clone()).with_config_env_vars(&env_vars);
+    /// assert_eq!(app_strategy.config_dir(), inner.config_dir());
+    ///
+    /// // Relative paths are ignored.
+    /// let env = HashMap::from([("FROBNICATOR_CONFIG", "config"), ("FROBNICATOR_HOME", config_path)]);
+    /// let inner = Xdg::new_with_env(args.clone(), env).unwrap();
+    /// let app_strategy = Override::new(inner).with_config_env_vars(&env_vars);
+    ///
+    /// assert_eq!(app_strategy.config_dir(), Path::new(config_path));
+    /// assert_eq!(
+    ///     app_strategy.config_dir_with_source().1,
+    ///     DirSource::EnvVar("FROBNICATOR_HOME")
+    /// );
+    ///
+    /// // The base directory is left to the wrapped strategy.
+    /// let env = HashMap::from([("XDG_CONFIG_HOME", config_path)]);
+    /// let inner = Xdg::new_with_env(args, env).unwrap();
+    /// let app_strategy = Override::new(inner.clone()).with_config_env_vars(&["FROBNICATOR_CONFIG", "XDG_CONFIG_HOME"]);
+    ///
+    /// assert_eq!(app_strategy.config_dir(), inner.config_dir());
+    /// assert_eq!(app_strategy.config_dir(), Path::new(config_path).join("frobnicator-plus/"));
+    /// # }
+    /// ```
+    pub fn with_config_env_vars(mut self, env_vars: &[&'static str]) -> Self {
+        self.config_dir.env_vars = env_vars.to_vec();
+        self
+    }
+
+    /// Overrides the data directory. Passing `None` keeps the wrapped strategy’s one.
+    pub fn with_data_dir(mut self, data_dir: impl Into<Option<PathBuf>>) -> Self {
+        self.data_dir.dir = data_dir.into();
+        self
+    }
+
+    /// Overrides the data directory with the first of the environment variables `env_vars` which is set to an absolute path. See [`with_config_env_vars`](#method.with_config_env_vars) for the details.
+    pub fn with_data_env_vars(mut self, env_vars: &[&'static str]) -> Self {
+        self.data_dir.env_vars = env_vars.to_vec();
+        self
+    }
+
+    /// Overrides the cache directory. Passing `None` keeps the wrapped strategy’s one.
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<Option<PathBuf>>) -> Self {
+        self.cache_dir.dir = cache_dir.into();
+        self
+    }
+
+    /// Overrides the cache directory with the first of the environment variables `env_vars` which is set to an absolute path. See [`with_config_env_vars`](#method.with_config_env_vars) for the details.
+    pub fn with_cache_env_vars(mut self, env_vars: &[&'static str]) -> Self {
+        self.cache_dir.env_vars = env_vars.to_vec();
+        self
+    }
+
+    /// Overrides the state directory. Passing `None` keeps the wrapped strategy’s one.
+    ///
+    /// The secret directory of app strategies moves along with it.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+    /// use etcetera::Override;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let state_path = if cfg!(windows) {
+    ///     "C:\\frobnicator\\state\\"
+    /// } else {
+    ///     "/frobnicator/state/"
+    /// };
+    ///
+    /// let inner = Xdg::new(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// }).unwrap();
+    /// let app_strategy = Override::new(inner).with_state_dir(PathBuf::from(state_path));
+    ///
+    /// assert_eq!(app_strategy.state_dir(), Some(PathBuf::from(state_path)));
+    /// assert_eq!(app_strategy.secret_dir(), Some(Path::new(state_path).join("secrets/")));
+    /// # }
+    /// ```
+    pub fn with_state_dir(mut self, state_dir: impl Into<Option<PathBuf>>) -> Self {
+        self.state_dir.dir = state_dir.into();
+        self
+    }
+
+    /// Overrides the state directory with the first of the environment variables `env_vars` which is set to an absolute path. See [`with_config_env_vars`](#method.with_config_env_vars) for the details.
+    pub fn with_state_env_vars(mut self, env_vars: &[&'static str]) -> Self {
+        self.state_dir.env_vars = env_vars.to_vec();
+        self
+    }
+
+    /// Overrides the runtime directory. Passing `None` keeps the wrapped strategy’s one.
+    pub fn with_runtime_dir(mut self, runtime_dir: impl Into<Option<PathBuf>>) -> Self {
+        self.runtime_dir.dir = runtime_dir.into();
+        self
+    }
+
+    /// Overrides the runtime directory with the first of the environment variables `env_vars` which is set to an absolute path. See [`with_config_env_vars`](#method.with_config_env_vars) for the details.
+    pub fn with_runtime_env_vars(mut self, env_vars: &[&'static str]) -> Self {
+        self.runtime_dir.env_vars = env_vars.to_vec();
+        self
+    }
+
+    /// Overrides the log directory. Passing `None` keeps the wrapped strategy’s one.
+    pub fn with_log_dir(mut self, log_dir: impl Into<Option<PathBuf>>) -> Self {
+        self.log_dir.dir = log_dir.into();
+        self
+    }
+
+    /// Overrides the log directory with the first of the environment variables `env_vars` which is set to an absolute path. See [`with_config_env_vars`](#method.with_config_env_vars) for the details.
+    pub fn with_log_env_vars(mut self, env_vars: &[&'static str]) -> Self {
+        self.log_dir.env_vars = env_vars.to_vec();
+        self
+    }
+
+    /// Overrides the binary directory. Passing `None` keeps the wrapped strategy’s one.
+    pub fn with_bin_dir(mut self, bin_dir: impl Into<Option<PathBuf>>) -> Self {
+        self.bin_dir.dir = bin_dir.into();
+        self
+    }
+
+    /// Overrides the binary directory with the first of the environment variables `env_vars` which is set to an absolute path. See [`with_config_env_vars`](#method.with_config_env_vars) for the details.
+    pub fn with_bin_env_vars(mut self, env_vars: &[&'static str]) -> Self {
+        self.bin_dir.env_vars = env_vars.to_vec();
+        self
+    }
+
+    /// Gets a reference to the wrapped strategy.
+    pub fn get_ref(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Unwraps the strategy, discarding the overrides.
+    pub fn into_inner(self) -> S {
+        self.strategy
+    }
+
+    fn is_overridden(&self, var_os: impl Fn(&str) -> Option<OsString>) -> bool {
+        [
+            &self.config_dir,
+            &self.data_dir,
+            &self.cache_dir,
+            &self.state_dir,
+            &self.runtime_dir,
+            &self.log_dir,
+            &self.bin_dir,
+        ]
+        .iter()
+        .any(|dir| dir.resolve(&var_os).is_some())
+    }
+}
+
+// The variables the strategies of this crate read their base directories from, which are shared by every application.
+const BASE_ENV_VARS: &[&str] = &[
+    "HOME",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "XDG_CONFIG_HOME",
+    "XDG_DATA_HOME",
+    "XDG_CACHE_HOME",
+    "XDG_STATE_HOME",
+    "XDG_BIN_HOME",
+    "XDG_RUNTIME_DIR",
+    "HOST_XDG_CONFIG_HOME",
+    "HOST_XDG_DATA_HOME",
+    "HOST_XDG_CACHE_HOME",
+    "HOST_XDG_STATE_HOME",
+];
+
+// The explicit directory, or the environment variables to read it from, of one of the overridden directories.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct OverriddenDir {
+    dir: Option<PathBuf>,
+    env_vars: Vec<&'static str>,
+}
+
+// The variables are read through the wrapped strategy’s `env_var_os`, so that they come from the same environment as its own.
+impl OverriddenDir {
+    fn resolve(&self, var_os: impl Fn(&str) -> Option<OsString>) -> Option<(PathBuf, DirSource)> {
+        if let Some(dir) = &self.dir {
+            return Some((dir.clone(), DirSource::Override));
+        }
+
+        self.env_vars.iter().find_map(|&env_var| {
+            if BASE_ENV_VARS.contains(&env_var) {
+                return None;
+            }
+
+            let dir = PathBuf::from(var_os(env_var)?);
+            dir.is_absolute()
+                .then_some((dir, DirSource::EnvVar(env_var)))
+        })
+    }
+
+    fn path(&self, var_os: impl Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        self.resolve(var_os).map(|(dir, _)| dir)
+    }
+}
+
+macro_rules! overridden {
+    ($($name: ident => $fallback: ident),* $(,)?; $($opt_name: ident),* $(,)?) => {
+        $(
+            fn $name(&self) -> PathBuf {
+                match self.$fallback.path(|key| self.strategy.env_var_os(key)) {
+                    Some(dir) => dir,
+                    None => self.strategy.$name(),
+                }
+            }
+        )*
+
+        $(
+            fn $opt_name(&self) -> Option<PathBuf> {
+                self.$opt_name.path(|key| self.strategy.env_var_os(key)).or_else(|| self.strategy.$opt_name())
+            }
+        )*
+
+        fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+            match self.runtime_dir.path(|key| self.strategy.env_var_os(key)) {
+                Some(dir) => Ok(dir),
+                None => self.strategy.runtime_dir_or_fallback(),
+            }
+        }
+
+        fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+            self.config_dir
+                .resolve(|key| self.strategy.env_var_os(key))
+                .unwrap_or_else(|| self.strategy.config_dir_with_source())
+        }
+
+        fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+            self.data_dir
+                .resolve(|key| self.strategy.env_var_os(key))
+                .unwrap_or_else(|| self.strategy.data_dir_with_source())
+        }
+
+        fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+            self.cache_dir
+                .resolve(|key| self.strategy.env_var_os(key))
+                .unwrap_or_else(|| self.strategy.cache_dir_with_source())
+        }
+
+        fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+            self.state_dir
+                .resolve(|key| self.strategy.env_var_os(key))
+                .or_else(|| self.strategy.state_dir_with_source())
+        }
+
+        fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+            self.runtime_dir
+                .resolve(|key| self.strategy.env_var_os(key))
+                .or_else(|| self.strategy.runtime_dir_with_source())
+        }
+    };
+}
+
+impl<S: BaseStrategy> BaseStrategy for Override<S> {
+    fn home_dir(&self) -> &Path {
+        self.strategy.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.strategy.env_var_os(key)
+    }
+
+    overridden!(
+        config_dir => config_dir,
+        data_dir => data_dir,
+        local_data_dir => data_dir,
+        cache_dir => cache_dir,
+        bin_dir => bin_dir;
+        state_dir,
+        runtime_dir,
+        log_dir,
+    );
+
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        self.strategy.desktop_dir()
+    }
+
+    fn documents_dir(&self) -> Option<PathBuf> {
+        self.strategy.documents_dir()
+    }
+
+    fn downloads_dir(&self) -> Option<PathBuf> {
+        self.strategy.downloads_dir()
+    }
+
+    fn music_dir(&self) -> Option<PathBuf> {
+        self.strategy.music_dir()
+    }
+
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        self.strategy.pictures_dir()
+    }
+
+    fn public_dir(&self) -> Option<PathBuf> {
+        self.strategy.public_dir()
+    }
+
+    fn templates_dir(&self) -> Option<PathBuf> {
+        self.strategy.templates_dir()
+    }
+
+    fn videos_dir(&self) -> Option<PathBuf> {
+        self.strategy.videos_dir()
+    }
+}
+
+impl<S: AppStrategy> AppStrategy for Override<S> {
+    fn home_dir(&self) -> &Path {
+        self.strategy.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.strategy.env_var_os(key)
+    }
+
+    overridden!(
+        config_dir => config_dir,
+        data_dir => data_dir,
+        local_data_dir => data_dir,
+        cache_dir => cache_dir,
+        bin_dir => bin_dir,
+        preference_dir => config_dir;
+        state_dir,
+        runtime_dir,
+        log_dir,
+    );
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.strategy.config_dirs()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.strategy.data_dirs()
+    }
+
+    // Like the default, this follows the state directory, so that the secrets move along with it.
+    fn secret_dir(&self) -> Option<PathBuf> {
+        match self.state_dir.path(|key| self.strategy.env_var_os(key)) {
+            Some(state_dir) => Some(state_dir.join("secrets/")),
+            None => self.strategy.secret_dir(),
+        }
+    }
+
+    // An overridden directory most likely lies outside of the wrapped strategy’s root.
+    fn app_root(&self) -> Option<PathBuf> {
+        if self.is_overridden(|key| self.strategy.env_var_os(key)) {
+            return None;
+        }
+
+        self.strategy.app_root()
+    }
+
+    // An explicit binary directory may well be shared, e.g. `/usr/local/bin`.
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        match self.bin_dir.resolve(|key| self.strategy.env_var_os(key)) {
+            Some(_) => None,
+            None => self.strategy.app_bin_dir(),
+        }
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.strategy.temp_dir()
+    }
+
+    fn scratch_dir(&self) -> io::Result<PathBuf> {
+        self.strategy.scratch_dir()
+    }
+}
@@ -0,0 +1,242 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::app_strategy::AppStrategy;
+use crate::base_strategy::BaseStrategy;
+use crate::DirSource;
+
+/// Wraps several strategies, and takes each directory from the first of them in which it already exists, or from the first strategy if it exists in none of them. This lets your application keep using an older layout, e.g. while migrating from [`Unix`](app_strategy/struct.Unix.html) to [`Xdg`](app_strategy/struct.Xdg.html), while new installations only ever use the first one.
+///
+/// Every directory is chosen on its own, so e.g. the configuration may come from the old layout and the cache from the new one. The home, binary & temporary directories always come from the first strategy, so that nothing new is written into the older layout. The [`app_root`](app_strategy/trait.AppStrategy.html#method.app_root) & [`app_bin_dir`](app_strategy/trait.AppStrategy.html#method.app_bin_dir) of app strategies follow the same rule as the other directories instead, so that [`remove_all`](app_strategy/trait.AppStrategy.html#method.remove_all) finds an older layout which is still there.
+///
+/// The strategies must all have the same type, so use [`AnyBaseStrategy`](base_strategy/enum.AnyBaseStrategy.html) or [`AnyAppStrategy`](app_strategy/enum.AnyAppStrategy.html) to combine different ones.
+///
+/// ```
+/// use etcetera::app_strategy::{AnyAppStrategy, AppStrategy, AppStrategyArgs, InMemory};
+/// use etcetera::PreferExisting;
+///
+/// let root_dir = std::env::temp_dir().join(format!("etcetera-prefer-existing-{}", std::process::id()));
+///
+/// let args = AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// };
+/// let new: AnyAppStrategy = InMemory::with_root_dir(args.clone(), root_dir.join("new")).unwrap().into();
+/// let old: AnyAppStrategy = InMemory::with_root_dir(args, root_dir.join("old")).unwrap().into();
+///
+/// // Only the old layout has a configuration directory so far.
+/// std::fs::create_dir_all(old.config_dir()).unwrap();
+///
+/// let app_strategy = PreferExisting::new(vec![new.clone(), old.clone()]).unwrap();
+/// assert_eq!(app_strategy.config_dir(), old.config_dir());
+/// assert_eq!(app_strategy.data_dir(), new.data_dir());
+/// assert_eq!(app_strategy.bin_dir(), new.bin_dir());
+///
+/// // There must be at least one strategy.
+/// assert!(PreferExisting::<AnyAppStrategy>::new(Vec::new()).is_none());
+///
+/// std::fs::remove_dir_all(&root_dir).unwrap();
+/// ```
+///
+/// ```
+/// # #[cfg(all(feature = "xdg", feature = "unix"))]
+/// # {
+/// use etcetera::app_strategy::{AnyAppStrategy, AppStrategy, AppStrategyArgs, Unix, Xdg};
+/// use etcetera::PreferExisting;
+///
+/// let home_dir = std::env::temp_dir().join(format!("etcetera-prefer-existing-root-{}", std::process::id()));
+///
+/// let args = AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// };
+/// let new: AnyAppStrategy = Xdg::new(args.clone()).unwrap().into();
+/// let old: AnyAppStrategy = Unix::with_home_dir(args, home_dir.clone()).unwrap().into();
+/// let app_strategy = PreferExisting::new(vec![new.clone(), old.clone()]).unwrap();
+/// assert_eq!(app_strategy.app_root(), None);
+///
+/// // `~/.frobnicator-plus/` is still around from an older version.
+/// std::fs::create_dir_all(old.app_root().unwrap()).unwrap();
+/// assert_eq!(app_strategy.app_root(), old.app_root());
+/// assert!(app_strategy.all_app_dirs().contains(&old.app_root().unwrap()));
+///
+/// std::fs::remove_dir_all(&home_dir).unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PreferExisting<S> {
+    strategies: Vec<S>,
+}
+
+impl<S> PreferExisting<S> {
+    /// Wraps `strategies`, the first of which is used for the directories which don’t exist yet.
+    /// This returns `None` if `strategies` is empty.
+    pub fn new(strategies: Vec<S>) -> Option<Self> {
+        if strategies.is_empty() {
+            return None;
+        }
+
+        Some(Self { strategies })
+    }
+
+    /// Gets the wrapped strategies, in order of preference.
+    pub fn strategies(&self) -> &[S] {
+        &self.strategies
+    }
+
+    /// Unwraps the strategies.
+    pub fn into_inner(self) -> Vec<S> {
+        self.strategies
+    }
+
+    fn first(&self) -> &S {
+        &self.strategies[0]
+    }
+
+    // Gets the first strategy for which `exists` is true, or the first strategy if there is none.
+    fn preferred(&self, exists: impl Fn(&S) -> bool) -> &S {
+        self.strategies
+            .iter()
+            .find(|strategy| exists(strategy))
+            .unwrap_or_else(|| self.first())
+    }
+}
+
+macro_rules! preferred {
+    ($($name: ident),* $(,)?; $($opt_name: ident),* $(,)?) => {
+        $(
+            fn $name(&self) -> PathBuf {
+                self.preferred(|strategy| strategy.$name().is_dir()).$name()
+            }
+        )*
+
+        $(
+            fn $opt_name(&self) -> Option<PathBuf> {
+                self.preferred(|strategy| strategy.$opt_name().is_some_and(|dir| dir.is_dir()))
+                    .$opt_name()
+            }
+        )*
+
+        fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+            self.preferred(|strategy| strategy.runtime_dir().is_some_and(|dir| dir.is_dir()))
+                .runtime_dir_or_fallback()
+        }
+
+        fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+            self.preferred(|strategy| strategy.config_dir().is_dir())
+                .config_dir_with_source()
+        }
+
+        fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+            self.preferred(|strategy| strategy.data_dir().is_dir())
+                .data_dir_with_source()
+        }
+
+        fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+            self.preferred(|strategy| strategy.cache_dir().is_dir())
+                .cache_dir_with_source()
+        }
+
+        fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+            self.preferred(|strategy| strategy.state_dir().is_some_and(|dir| dir.is_dir()))
+                .state_dir_with_source()
+        }
+
+        fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+            self.preferred(|strategy| strategy.runtime_dir().is_some_and(|dir| dir.is_dir()))
+                .runtime_dir_with_source()
+        }
+    };
+}
+
+impl<S: BaseStrategy> BaseStrategy for PreferExisting<S> {
+    fn home_dir(&self) -> &Path {
+        self.first().home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.first().env_var_os(key)
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.first().bin_dir()
+    }
+
+    preferred!(
+        config_dir,
+        data_dir,
+        local_data_dir,
+        cache_dir;
+        state_dir,
+        runtime_dir,
+        log_dir,
+        desktop_dir,
+        documents_dir,
+        downloads_dir,
+        music_dir,
+        pictures_dir,
+        public_dir,
+        templates_dir,
+        videos_dir,
+    );
+}
+
+impl<S: AppStrategy> AppStrategy for PreferExisting<S> {
+    fn home_dir(&self) -> &Path {
+        self.first().home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.first().env_var_os(key)
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.first().bin_dir()
+    }
+
+    preferred!(
+        config_dir,
+        data_dir,
+        local_data_dir,
+        cache_dir,
+        preference_dir;
+        state_dir,
+        runtime_dir,
+        log_dir,
+        secret_dir,
+    );
+
+    // The system-wide directories come after the user’s one, so they follow it.
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.preferred(|strategy| strategy.config_dir().is_dir())
+            .config_dirs()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.preferred(|strategy| strategy.data_dir().is_dir())
+            .data_dirs()
+    }
+
+    fn app_root(&self) -> Option<PathBuf> {
+        self.preferred(|strategy| strategy.app_root().is_some_and(|dir| dir.is_dir()))
+            .app_root()
+    }
+
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        self.preferred(|strategy| strategy.app_bin_dir().is_some_and(|dir| dir.is_dir()))
+            .app_bin_dir()
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.first().temp_dir()
+    }
+
+    fn scratch_dir(&self) -> io::Result<PathBuf> {
+        self.first().scratch_dir()
+    }
+}
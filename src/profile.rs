@@ -0,0 +1,186 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::app_strategy::AppStrategy;
+use crate::DirSource;
+
+/// Wraps an [`AppStrategy`](app_strategy/trait.AppStrategy.html), and appends a profile’s name to each of your application’s directories, e.g. `~/.config/frobnicator-plus/work/`. This is handy to implement a `--profile` flag, as each profile then has its own configuration, data & cache.
+///
+/// The name is slugified first: it is lowercased, every run of characters other than ASCII letters, digits, `-`, `_` & `.` becomes a single `-`, and leading & trailing `-` & `.` are removed. If nothing is left, e.g. for an empty name, no component is appended at all, so the default profile can simply use an empty name.
+///
+/// The system-wide [`config_dirs`](app_strategy/trait.AppStrategy.html#method.config_dirs) & [`data_dirs`](app_strategy/trait.AppStrategy.html#method.data_dirs) are shared by every profile, as is the [`bin_dir`](app_strategy/trait.AppStrategy.html#tymethod.bin_dir).
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+/// use etcetera::Profile;
+///
+/// let inner = Xdg::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// }).unwrap();
+///
+/// let app_strategy = Profile::new(inner.clone(), "Work Laptop");
+/// assert_eq!(app_strategy.name(), Some("work-laptop"));
+/// assert_eq!(app_strategy.config_dir(), inner.config_dir().join("work-laptop"));
+/// assert_eq!(app_strategy.cache_dir(), inner.cache_dir().join("work-laptop"));
+/// assert_eq!(app_strategy.bin_dir(), inner.bin_dir());
+///
+/// let app_strategy = Profile::new(inner.clone(), "../..");
+/// assert_eq!(app_strategy.name(), None);
+/// assert_eq!(app_strategy.config_dir(), inner.config_dir());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Profile<S> {
+    strategy: S,
+    name: Option<String>,
+}
+
+impl<S> Profile<S> {
+    /// Wraps `strategy`, namespacing its directories by the slugified `name`.
+    pub fn new(strategy: S, name: &str) -> Self {
+        let name = slugify(name);
+        Self {
+            strategy,
+            name: (!name.is_empty()).then_some(name),
+        }
+    }
+
+    /// Gets the slugified name of the profile, which is appended to the directories, or `None` if nothing is appended.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets a reference to the wrapped strategy.
+    pub fn get_ref(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Unwraps the strategy, discarding the profile.
+    pub fn into_inner(self) -> S {
+        self.strategy
+    }
+
+    fn namespace(&self, dir: PathBuf) -> PathBuf {
+        match &self.name {
+            Some(name) => dir.join(name),
+            None => dir,
+        }
+    }
+}
+
+fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    // This also rules out `.` & `..`, which would escape the application’s directories.
+    slug.trim_matches(['-', '.']).to_string()
+}
+
+macro_rules! namespaced {
+    ($($name: ident),* $(,)?; $($opt_name: ident),* $(,)?) => {
+        $(
+            fn $name(&self) -> PathBuf {
+                self.namespace(self.strategy.$name())
+            }
+        )*
+
+        $(
+            fn $opt_name(&self) -> Option<PathBuf> {
+                self.strategy.$opt_name().map(|dir| self.namespace(dir))
+            }
+        )*
+    };
+}
+
+impl<S: AppStrategy> AppStrategy for Profile<S> {
+    fn home_dir(&self) -> &Path {
+        self.strategy.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.strategy.env_var_os(key)
+    }
+
+    namespaced!(
+        config_dir,
+        data_dir,
+        local_data_dir,
+        cache_dir,
+        preference_dir;
+        state_dir,
+        runtime_dir,
+        log_dir,
+        secret_dir,
+    );
+
+    fn bin_dir(&self) -> PathBuf {
+        self.strategy.bin_dir()
+    }
+
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        self.strategy
+            .runtime_dir_or_fallback()
+            .map(|dir| self.namespace(dir))
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.strategy.config_dirs()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.strategy.data_dirs()
+    }
+
+    fn app_root(&self) -> Option<PathBuf> {
+        self.strategy.app_root()
+    }
+
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        self.strategy.app_bin_dir()
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.strategy.temp_dir()
+    }
+
+    fn scratch_dir(&self) -> io::Result<PathBuf> {
+        self.strategy.scratch_dir()
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (dir, source) = self.strategy.config_dir_with_source();
+        (self.namespace(dir), source)
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (dir, source) = self.strategy.data_dir_with_source();
+        (self.namespace(dir), source)
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (dir, source) = self.strategy.cache_dir_with_source();
+        (self.namespace(dir), source)
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        let (dir, source) = self.strategy.state_dir_with_source()?;
+        Some((self.namespace(dir), source))
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        let (dir, source) = self.strategy.runtime_dir_with_source()?;
+        Some((self.namespace(dir), source))
+    }
+}
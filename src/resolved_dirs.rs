@@ -0,0 +1,155 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::base_strategy::BaseStrategy;
+use crate::{DirClassification, DirKind};
+
+/// Every directory a strategy resolves, computed once. This is returned by the `snapshot` methods of [`BaseStrategy`](base_strategy/trait.BaseStrategy.html#method.snapshot) & [`AppStrategy`](app_strategy/trait.AppStrategy.html#method.snapshot).
+///
+/// It is meant for logging, diagnostics & golden tests. Its `Display` implementation lists every directory on its own line, labeled, which is handy for bug reports. With the `serde` feature enabled, this implements `Serialize` & `Deserialize` using the field names below.
+///
+/// ```
+/// use etcetera::base_strategy::{BaseStrategy, Custom};
+/// use etcetera::ResolvedDirs;
+/// use std::path::PathBuf;
+///
+/// let home_path = if cfg!(windows) { "C:\\home\\" } else { "/home/" };
+/// let home_dir = PathBuf::from(home_path);
+///
+/// let base_strategy = Custom::builder(home_path).build().unwrap();
+///
+/// assert_eq!(
+///     base_strategy.snapshot(),
+///     ResolvedDirs {
+///         home_dir: home_dir.clone(),
+///         config_dir: home_dir.join(".config/"),
+///         data_dir: home_dir.join(".local/share/"),
+///         cache_dir: home_dir.join(".cache/"),
+///         state_dir: None,
+///         runtime_dir: None,
+///         log_dir: None,
+///         bin_dir: home_dir.join(".local/bin/"),
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResolvedDirs {
+    /// The home directory.
+    pub home_dir: PathBuf,
+    /// The configuration directory.
+    pub config_dir: PathBuf,
+    /// The data directory.
+    pub data_dir: PathBuf,
+    /// The cache directory.
+    pub cache_dir: PathBuf,
+    /// The state directory, if the strategy has one.
+    pub state_dir: Option<PathBuf>,
+    /// The runtime directory, if the strategy has one.
+    pub runtime_dir: Option<PathBuf>,
+    /// The log directory, if the strategy has one.
+    pub log_dir: Option<PathBuf>,
+    /// The binary directory.
+    pub bin_dir: PathBuf,
+}
+
+impl ResolvedDirs {
+    /// Finds out which of these directories `path` lies in, and what is left of `path` relative to it. The home directory isn’t considered, as every other directory usually lies inside it.
+    ///
+    /// As directories may be nested, e.g. the log directory of the XDG strategy is inside its state directory, the innermost one containing `path` wins. If several directories are the same, e.g. the configuration & data directories on Windows, the first one in the order of [`DirKind`](enum.DirKind.html) wins.
+    /// Like [`is_within_home`](base_strategy/trait.BaseStrategy.html#method.is_within_home), the comparison is lexical, and paths containing `..` are never classified.
+    ///
+    /// ```
+    /// # #[cfg(feature = "xdg")]
+    /// # {
+    /// use etcetera::base_strategy::{BaseStrategy, Xdg};
+    /// use etcetera::DirKind;
+    /// use std::collections::HashMap;
+    /// use std::path::Path;
+    ///
+    /// let base_strategy = Xdg::new_with_env(HashMap::<&str, &str>::new()).unwrap();
+    /// let home_dir = base_strategy.home_dir();
+    ///
+    /// let classification = base_strategy
+    ///     .classify(&home_dir.join(".local/state/logs/frobnicator.log"))
+    ///     .unwrap();
+    /// assert_eq!(classification.kind, DirKind::Log);
+    /// assert_eq!(classification.dir, base_strategy.log_dir().unwrap());
+    /// assert_eq!(classification.relative, Path::new("frobnicator.log"));
+    ///
+    /// let classification = base_strategy.classify(&base_strategy.config_dir()).unwrap();
+    /// assert_eq!(classification.kind, DirKind::Config);
+    /// assert_eq!(classification.relative, Path::new(""));
+    ///
+    /// assert_eq!(base_strategy.classify(&home_dir.join("Documents")), None);
+    /// assert_eq!(base_strategy.classify(&home_dir.join(".config/../Documents")), None);
+    /// # }
+    /// ```
+    pub fn classify(&self, path: &Path) -> Option<DirClassification> {
+        let dirs = [
+            (DirKind::Config, Some(&self.config_dir)),
+            (DirKind::Data, Some(&self.data_dir)),
+            (DirKind::Cache, Some(&self.cache_dir)),
+            (DirKind::State, self.state_dir.as_ref()),
+            (DirKind::Runtime, self.runtime_dir.as_ref()),
+            (DirKind::Log, self.log_dir.as_ref()),
+            (DirKind::Bin, Some(&self.bin_dir)),
+        ];
+
+        let (kind, dir) = dirs
+            .into_iter()
+            .filter_map(|(kind, dir)| Some((kind, dir?)))
+            .filter(|(_, dir)| crate::is_within(path, dir))
+            // `max_by_key` returns the last maximum, so the order is reversed to let the first one win.
+            .rev()
+            .max_by_key(|(_, dir)| dir.components().count())?;
+
+        Some(DirClassification {
+            kind,
+            dir: dir.clone(),
+            relative: path.strip_prefix(dir).ok()?.to_path_buf(),
+        })
+    }
+}
+
+impl fmt::Display for ResolvedDirs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn line(f: &mut fmt::Formatter<'_>, label: &str, dir: Option<&Path>) -> fmt::Result {
+            match dir {
+                Some(dir) => writeln!(f, "{label}: {}", dir.display()),
+                None => writeln!(f, "{label}: None"),
+            }
+        }
+
+        line(f, "home_dir", Some(&self.home_dir))?;
+        line(f, "config_dir", Some(&self.config_dir))?;
+        line(f, "data_dir", Some(&self.data_dir))?;
+        line(f, "cache_dir", Some(&self.cache_dir))?;
+        line(f, "state_dir", self.state_dir.as_deref())?;
+        line(f, "runtime_dir", self.runtime_dir.as_deref())?;
+        line(f, "log_dir", self.log_dir.as_deref())?;
+        line(f, "bin_dir", Some(&self.bin_dir))
+    }
+}
+
+/// Lists every directory `strategy` resolves, one labeled line each, e.g. `config_dir: /home/user/.config/`. Directories the strategy doesn’t have are listed as `None`.
+/// This is the same as `strategy.snapshot().to_string()`, which also works for an [`AppStrategy`](app_strategy/trait.AppStrategy.html).
+///
+/// ```
+/// use etcetera::base_strategy::Custom;
+///
+/// let home_path = if cfg!(windows) { "C:\\home" } else { "/home" };
+/// let sep = std::path::MAIN_SEPARATOR;
+///
+/// let base_strategy = Custom::builder(home_path)
+///     .config_dir(format!("{home_path}{sep}config"))
+///     .build()
+///     .unwrap();
+/// let description = etcetera::describe(&base_strategy);
+///
+/// assert!(description.contains(&format!("config_dir: {home_path}{sep}config\n")));
+/// assert!(description.contains("runtime_dir: None\n"));
+/// ```
+pub fn describe<S: BaseStrategy + ?Sized>(strategy: &S) -> String {
+    strategy.snapshot().to_string()
+}
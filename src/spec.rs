@@ -0,0 +1,137 @@
+//! The path logic of the [XDG Base Directory Specification](https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html), without any I/O.
+//!
+//! The [`Xdg`](../base_strategy/struct.Xdg.html) strategy reads the environment & builds `PathBuf`s on top of this module. If you don’t have `std`, e.g. on an embedded target or a custom runtime, you can use it directly by disabling the default `std` feature: it only needs `alloc`, and you pass in the values of the environment variables yourself.
+//!
+//! Paths are plain strings with `/` separators, and only values starting with `/` count as absolute.
+//!
+//! ```
+//! use etcetera::spec::{BaseDir, SearchDirs};
+//!
+//! assert_eq!(BaseDir::Config.resolve("/home/user", None), "/home/user/.config/");
+//! assert_eq!(BaseDir::Config.resolve("/home/user", Some("/config/")), "/config/");
+//!
+//! // Relative values must be ignored.
+//! assert_eq!(BaseDir::Data.resolve("/home/user/", Some("data")), "/home/user/.local/share/");
+//!
+//! assert_eq!(SearchDirs::Config.resolve(Some("/a/::relative:/b/")), ["/a/", "/b/"]);
+//! assert_eq!(SearchDirs::Data.resolve(None), ["/usr/local/share/", "/usr/share/"]);
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A per-user base directory, which lives in the home directory unless its environment variable says otherwise.
+///
+/// The runtime directory has no default, so it isn’t listed here. See [`runtime_dir`](fn.runtime_dir.html) instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum BaseDir {
+    /// `XDG_CONFIG_HOME`, which defaults to `~/.config/`.
+    Config,
+    /// `XDG_DATA_HOME`, which defaults to `~/.local/share/`.
+    Data,
+    /// `XDG_CACHE_HOME`, which defaults to `~/.cache/`.
+    Cache,
+    /// `XDG_STATE_HOME`, which defaults to `~/.local/state/`.
+    State,
+    /// `XDG_BIN_HOME`, which defaults to `~/.local/bin/`.
+    Bin,
+}
+
+impl BaseDir {
+    /// Gets the name of the environment variable which overrides this directory.
+    pub const fn env_var(self) -> &'static str {
+        match self {
+            Self::Config => "XDG_CONFIG_HOME",
+            Self::Data => "XDG_DATA_HOME",
+            Self::Cache => "XDG_CACHE_HOME",
+            Self::State => "XDG_STATE_HOME",
+            Self::Bin => "XDG_BIN_HOME",
+        }
+    }
+
+    /// Gets the default location of this directory, relative to the home directory.
+    pub const fn default_path(self) -> &'static str {
+        match self {
+            Self::Config => ".config/",
+            Self::Data => ".local/share/",
+            Self::Cache => ".cache/",
+            Self::State => ".local/state/",
+            Self::Bin => ".local/bin/",
+        }
+    }
+
+    /// Resolves this directory from the home directory & the value of its [environment variable](#method.env_var), if it is set.
+    pub fn resolve(self, home_dir: &str, value: Option<&str>) -> String {
+        match value.filter(|value| is_absolute(value)) {
+            Some(value) => String::from(value),
+            None => join(home_dir, self.default_path()),
+        }
+    }
+}
+
+/// A preference-ordered list of system-wide directories, which are searched after the matching [`BaseDir`](enum.BaseDir.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum SearchDirs {
+    /// `XDG_CONFIG_DIRS`, which defaults to `/etc/xdg/`.
+    Config,
+    /// `XDG_DATA_DIRS`, which defaults to `/usr/local/share/` & `/usr/share/`.
+    Data,
+}
+
+impl SearchDirs {
+    /// Gets the name of the environment variable which overrides these directories.
+    pub const fn env_var(self) -> &'static str {
+        match self {
+            Self::Config => "XDG_CONFIG_DIRS",
+            Self::Data => "XDG_DATA_DIRS",
+        }
+    }
+
+    /// Gets the directories used when the [environment variable](#method.env_var) is unset or contains no absolute paths.
+    pub const fn defaults(self) -> &'static [&'static str] {
+        match self {
+            Self::Config => &["/etc/xdg/"],
+            Self::Data => &["/usr/local/share/", "/usr/share/"],
+        }
+    }
+
+    /// Resolves these directories from the `:`-separated value of their [environment variable](#method.env_var), if it is set.
+    /// The separator is always `:`, as paths are Unix-style here. The [`Xdg`](../base_strategy/struct.Xdg.html) strategy splits on `;` on Windows instead, where `:` is part of drive letters.
+    /// Empty & relative entries are ignored, as if they weren’t in the list at all.
+    pub fn resolve(self, value: Option<&str>) -> Vec<String> {
+        let dirs: Vec<String> = value
+            .unwrap_or_default()
+            .split(':')
+            .filter(|dir| is_absolute(dir))
+            .map(String::from)
+            .collect();
+
+        if dirs.is_empty() {
+            self.defaults().iter().copied().map(String::from).collect()
+        } else {
+            dirs
+        }
+    }
+}
+
+/// Resolves the runtime directory from the value of `XDG_RUNTIME_DIR`, if it is set.
+/// There is no default, so this is `None` unless the value is absolute.
+pub fn runtime_dir(value: Option<&str>) -> Option<String> {
+    value.filter(|value| is_absolute(value)).map(String::from)
+}
+
+fn is_absolute(path: &str) -> bool {
+    path.starts_with('/')
+}
+
+fn join(home_dir: &str, path: &str) -> String {
+    let mut joined = String::from(home_dir);
+    if !joined.ends_with('/') {
+        joined.push('/');
+    }
+    joined.push_str(path);
+
+    joined
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::app_strategy::{AnyAppStrategy, AppStrategyArgs};
+use crate::base_strategy::BaseStrategy;
+use crate::{CreationError, HomeDirError};
+
+/// Names one of the directory layouts of this crate, e.g. to pick one from a command line flag or a configuration file.
+///
+/// This is parsed from & displayed as `xdg`, `windows`, `apple` or `unix`, ignoring case when parsing. Only the kinds whose Cargo feature is enabled exist. The same names are accepted by the `ETCETERA_STRATEGY` environment variable, see [`choose_base_strategy_enum`](base_strategy/fn.choose_base_strategy_enum.html).
+///
+/// ```
+/// # #[cfg(all(feature = "xdg", feature = "windows", feature = "apple", feature = "unix"))]
+/// # {
+/// use etcetera::base_strategy::{BaseStrategy, Windows};
+/// use etcetera::StrategyKind;
+///
+/// let kind: StrategyKind = "Windows".parse().unwrap();
+/// assert_eq!(kind, StrategyKind::Windows);
+/// assert_eq!(kind.to_string(), "windows");
+///
+/// let base_strategy = kind.base_strategy().unwrap();
+/// assert_eq!(base_strategy.config_dir(), Windows::new().unwrap().config_dir());
+///
+/// let err = "beos".parse::<StrategyKind>().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "unknown directory strategy \"beos\", expected one of xdg, windows, apple or unix"
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum StrategyKind {
+    /// The [XDG Base Directory Specification](https://specifications.freedesktop.org/basedir-spec/latest/), see [`base_strategy::Xdg`](base_strategy/struct.Xdg.html).
+    #[cfg(feature = "xdg")]
+    Xdg,
+    /// The Windows layout, see [`base_strategy::Windows`](base_strategy/struct.Windows.html).
+    #[cfg(feature = "windows")]
+    Windows,
+    /// The macOS layout, see [`base_strategy::Apple`](base_strategy/struct.Apple.html).
+    #[cfg(feature = "apple")]
+    Apple,
+    /// A single hidden folder in the home directory, see [`app_strategy::Unix`](app_strategy/struct.Unix.html).
+    #[cfg(feature = "unix")]
+    Unix,
+}
+
+impl StrategyKind {
+    /// Every kind of strategy, in the order they are declared in, e.g. to list the possible values of a command line flag.
+    pub const ALL: &'static [Self] = &[
+        #[cfg(feature = "xdg")]
+        Self::Xdg,
+        #[cfg(feature = "windows")]
+        Self::Windows,
+        #[cfg(feature = "apple")]
+        Self::Apple,
+        #[cfg(feature = "unix")]
+        Self::Unix,
+    ];
+
+    /// Gets the lowercase name of this kind, which is also what it is displayed as.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            #[cfg(feature = "xdg")]
+            Self::Xdg => "xdg",
+            #[cfg(feature = "windows")]
+            Self::Windows => "windows",
+            #[cfg(feature = "apple")]
+            Self::Apple => "apple",
+            #[cfg(feature = "unix")]
+            Self::Unix => "unix",
+        }
+    }
+
+    /// Creates the [`BaseStrategy`](base_strategy/trait.BaseStrategy.html) of this kind.
+    ///
+    /// As the Unix layout only exists for applications, `Unix` creates the [`Xdg`](base_strategy/struct.Xdg.html) base strategy, which is what command line tools on Unix use for everything but their own files. If the `xdg` feature is disabled, it creates the one [`choose_base_strategy`](base_strategy/fn.choose_base_strategy.html) returns instead.
+    pub fn base_strategy(self) -> Result<Box<dyn BaseStrategy>, HomeDirError> {
+        match self {
+            #[cfg(feature = "xdg")]
+            Self::Xdg => Ok(Box::new(crate::base_strategy::Xdg::new()?)),
+            #[cfg(feature = "windows")]
+            Self::Windows => Ok(Box::new(crate::base_strategy::Windows::new()?)),
+            #[cfg(feature = "apple")]
+            Self::Apple => Ok(Box::new(crate::base_strategy::Apple::new()?)),
+            #[cfg(all(feature = "unix", feature = "xdg"))]
+            Self::Unix => Ok(Box::new(crate::base_strategy::Xdg::new()?)),
+            #[cfg(all(feature = "unix", not(feature = "xdg")))]
+            Self::Unix => Ok(Box::new(crate::base_strategy::choose_base_strategy()?)),
+        }
+    }
+
+    /// Creates the [`AppStrategy`](app_strategy/trait.AppStrategy.html) of this kind, wrapped in an [`AnyAppStrategy`](app_strategy/enum.AnyAppStrategy.html) as `AppStrategy` cannot be boxed.
+    ///
+    /// ```
+    /// # #[cfg(feature = "unix")]
+    /// # {
+    /// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Unix};
+    /// use etcetera::StrategyKind;
+    ///
+    /// let args = AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let app_strategy = StrategyKind::Unix.app_strategy(args.clone()).unwrap();
+    /// assert_eq!(app_strategy.config_dir(), Unix::new(args).unwrap().config_dir());
+    /// # }
+    /// ```
+    // Without any strategies, there is nothing to pass `args` to.
+    #[cfg_attr(
+        not(any(
+            feature = "xdg",
+            feature = "windows",
+            feature = "apple",
+            feature = "unix"
+        )),
+        allow(unused_variables)
+    )]
+    pub fn app_strategy(self, args: AppStrategyArgs) -> Result<AnyAppStrategy, CreationError> {
+        match self {
+            #[cfg(feature = "xdg")]
+            Self::Xdg => Ok(crate::app_strategy::Xdg::new(args)?.into()),
+            #[cfg(feature = "windows")]
+            Self::Windows => Ok(crate::app_strategy::Windows::new(args)?.into()),
+            #[cfg(feature = "apple")]
+            Self::Apple => Ok(crate::app_strategy::Apple::new(args)?.into()),
+            #[cfg(feature = "unix")]
+            Self::Unix => Ok(crate::app_strategy::Unix::new(args)?.into()),
+        }
+    }
+}
+
+impl fmt::Display for StrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StrategyKind {
+    type Err = ParseStrategyKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|kind| s.eq_ignore_ascii_case(kind.as_str()))
+            .ok_or_else(|| ParseStrategyKindError {
+                input: s.to_string(),
+            })
+    }
+}
+
+/// The error returned when parsing a [`StrategyKind`](enum.StrategyKind.html) from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStrategyKindError {
+    input: String,
+}
+
+impl ParseStrategyKindError {
+    /// Gets the name which could not be parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl fmt::Display for ParseStrategyKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown directory strategy {:?}", self.input)?;
+
+        let names: Vec<_> = StrategyKind::ALL.iter().map(|kind| kind.as_str()).collect();
+        match names.split_last() {
+            Some((last, [])) => write!(f, ", expected {last}"),
+            Some((last, rest)) => write!(f, ", expected one of {} or {last}", rest.join(", ")),
+            None => write!(f, ", as every strategy is disabled"),
+        }
+    }
+}
+
+impl std::error::Error for ParseStrategyKindError {}
@@ -0,0 +1,124 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// This strategy keeps everything in a fresh, uniquely-named folder inside the temporary directory, which is removed again when the strategy is dropped. It is meant for the tests of applications using this crate, so that they never read or write the real user’s files, & is only available with the `test-util` feature.
+///
+/// It implements both [`BaseStrategy`](base_strategy/trait.BaseStrategy.html) & [`AppStrategy`](app_strategy/trait.AppStrategy.html), with the same directories for both: as every instance has a folder of its own, there is no need to append your application’s name. The layout is the same as that of the [`Portable`](base_strategy/struct.Portable.html) strategy, except that the runtime directory is always there, & the temporary directory of `AppStrategy` is inside the root folder too.
+///
+/// Only the root folder is created, the directories inside it are left for your application to create.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::TempStrategy;
+/// use std::path::Path;
+///
+/// let temp_strategy = TempStrategy::new().unwrap();
+/// let root_dir = temp_strategy.root_dir().to_path_buf();
+///
+/// assert!(root_dir.is_absolute());
+/// assert!(root_dir.is_dir());
+/// assert_eq!(AppStrategy::home_dir(&temp_strategy), root_dir);
+/// assert_eq!(
+///     AppStrategy::config_dir(&temp_strategy).strip_prefix(&root_dir),
+///     Ok(Path::new("config/"))
+/// );
+/// assert_eq!(
+///     AppStrategy::runtime_dir(&temp_strategy).unwrap().strip_prefix(&root_dir),
+///     Ok(Path::new("runtime/"))
+/// );
+/// assert_eq!(
+///     temp_strategy.temp_dir().strip_prefix(&root_dir),
+///     Ok(Path::new("tmp/"))
+/// );
+///
+/// // Every instance gets a folder of its own.
+/// assert_ne!(TempStrategy::new().unwrap().root_dir(), root_dir);
+///
+/// let config_file = temp_strategy.config_file("frobnicator.toml").unwrap();
+/// std::fs::write(config_file, "answer = 42").unwrap();
+///
+/// drop(temp_strategy);
+/// assert!(!root_dir.exists());
+/// ```
+#[derive(Debug)]
+pub struct TempStrategy {
+    root_dir: PathBuf,
+}
+
+impl TempStrategy {
+    /// Creates a new, empty folder inside [`std::env::temp_dir`] & a TempStrategy rooted at it.
+    /// This returns an error if the folder cannot be created.
+    pub fn new() -> io::Result<Self> {
+        let temp_dir = std::env::temp_dir();
+        // `TMPDIR` may be set to a relative path.
+        let temp_dir = if temp_dir.is_absolute() {
+            temp_dir
+        } else {
+            std::env::current_dir()?.join(temp_dir)
+        };
+
+        let root_dir = crate::app_strategy::create_unique_dir(&temp_dir, "etcetera-test-")?;
+        Ok(Self { root_dir })
+    }
+
+    /// Gets the folder everything is kept in, which is removed when the strategy is dropped.
+    pub fn root_dir(&self) -> &Path {
+        &self.root_dir
+    }
+}
+
+impl Drop for TempStrategy {
+    fn drop(&mut self) {
+        // Failing to clean up must not turn into a panic while a failed test is unwinding.
+        let _ = std::fs::remove_dir_all(&self.root_dir);
+    }
+}
+
+// Both traits get the same directories, as described above.
+macro_rules! impl_strategy {
+    ($strategy_trait: path { $($extra: item)* }) => {
+        impl $strategy_trait for TempStrategy {
+            fn home_dir(&self) -> &Path {
+                &self.root_dir
+            }
+
+            fn config_dir(&self) -> PathBuf {
+                self.root_dir.join("config/")
+            }
+
+            fn data_dir(&self) -> PathBuf {
+                self.root_dir.join("data/")
+            }
+
+            fn cache_dir(&self) -> PathBuf {
+                self.root_dir.join("cache/")
+            }
+
+            fn state_dir(&self) -> Option<PathBuf> {
+                Some(self.root_dir.join("state/"))
+            }
+
+            fn runtime_dir(&self) -> Option<PathBuf> {
+                Some(self.root_dir.join("runtime/"))
+            }
+
+            fn log_dir(&self) -> Option<PathBuf> {
+                Some(self.root_dir.join("logs/"))
+            }
+
+            fn bin_dir(&self) -> PathBuf {
+                self.root_dir.join("bin/")
+            }
+
+            $($extra)*
+        }
+    };
+}
+
+impl_strategy!(crate::base_strategy::BaseStrategy {});
+
+impl_strategy!(crate::app_strategy::AppStrategy {
+    fn temp_dir(&self) -> PathBuf {
+        self.root_dir.join("tmp/")
+    }
+});
@@ -0,0 +1,192 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::app_strategy::AppStrategy;
+use crate::{CreationError, DirSource};
+
+/// Wraps an [`AppStrategy`](app_strategy/trait.AppStrategy.html), and appends a version to your application’s data & state directories, e.g. `~/.local/share/frobnicator-plus/v2/`. This keeps the on-disk data of each major version apart, so that a schema migration can be rolled back by simply running the previous version again.
+///
+/// The configuration & preference directories are shared by every version by default, as users expect their settings to survive upgrades. Use [`with_versioned_config`](#method.with_versioned_config) to version them too. The cache, runtime, log, binary & system-wide directories are never versioned.
+///
+/// ```
+/// # #[cfg(feature = "xdg")]
+/// # {
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Xdg};
+/// use etcetera::Versioned;
+///
+/// let inner = Xdg::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+///     ..Default::default()
+/// }).unwrap();
+///
+/// let app_strategy = Versioned::new(inner.clone(), "v2").unwrap();
+/// assert_eq!(app_strategy.data_dir(), inner.data_dir().join("v2"));
+/// assert_eq!(app_strategy.state_dir(), inner.state_dir().map(|dir| dir.join("v2")));
+/// assert_eq!(app_strategy.config_dir(), inner.config_dir());
+///
+/// let app_strategy = app_strategy.with_versioned_config(true);
+/// assert_eq!(app_strategy.config_dir(), inner.config_dir().join("v2"));
+///
+/// // The version must be a single folder name.
+/// assert!(Versioned::new(inner, "v2/../..").is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Versioned<S> {
+    strategy: S,
+    version: String,
+    versioned_config: bool,
+}
+
+impl<S> Versioned<S> {
+    /// Wraps `strategy`, appending `version` to its data & state directories.
+    /// This returns an error if `version` cannot be used as a folder name, following the same rules as [`AppStrategyArgs::validate`](app_strategy/struct.AppStrategyArgs.html#method.validate).
+    pub fn new(strategy: S, version: impl Into<String>) -> Result<Self, CreationError> {
+        let version = version.into();
+        if let Err(reason) = crate::app_strategy::validate_component(&version) {
+            return Err(CreationError::InvalidVersion { version, reason });
+        }
+
+        Ok(Self {
+            strategy,
+            version,
+            versioned_config: false,
+        })
+    }
+
+    /// Sets whether the version is appended to the configuration & preference directories too. This is disabled by default.
+    pub fn with_versioned_config(mut self, versioned_config: bool) -> Self {
+        self.versioned_config = versioned_config;
+        self
+    }
+
+    /// Gets the version appended to the directories.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Gets a reference to the wrapped strategy.
+    pub fn get_ref(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Unwraps the strategy, discarding the version.
+    pub fn into_inner(self) -> S {
+        self.strategy
+    }
+
+    fn config(&self, dir: PathBuf) -> PathBuf {
+        if self.versioned_config {
+            dir.join(&self.version)
+        } else {
+            dir
+        }
+    }
+}
+
+impl<S: AppStrategy> AppStrategy for Versioned<S> {
+    fn home_dir(&self) -> &Path {
+        self.strategy.home_dir()
+    }
+
+    fn env_var_os(&self, key: &str) -> Option<OsString> {
+        self.strategy.env_var_os(key)
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.config(self.strategy.config_dir())
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.strategy.data_dir().join(&self.version)
+    }
+
+    fn local_data_dir(&self) -> PathBuf {
+        self.strategy.local_data_dir().join(&self.version)
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.strategy.cache_dir()
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.strategy
+            .state_dir()
+            .map(|state_dir| state_dir.join(&self.version))
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.strategy.runtime_dir()
+    }
+
+    fn runtime_dir_or_fallback(&self) -> io::Result<PathBuf> {
+        self.strategy.runtime_dir_or_fallback()
+    }
+
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.strategy.log_dir()
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.strategy.bin_dir()
+    }
+
+    fn preference_dir(&self) -> PathBuf {
+        self.config(self.strategy.preference_dir())
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.strategy.config_dirs()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.strategy.data_dirs()
+    }
+
+    fn secret_dir(&self) -> Option<PathBuf> {
+        self.strategy.secret_dir()
+    }
+
+    fn app_root(&self) -> Option<PathBuf> {
+        self.strategy.app_root()
+    }
+
+    fn app_bin_dir(&self) -> Option<PathBuf> {
+        self.strategy.app_bin_dir()
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.strategy.temp_dir()
+    }
+
+    fn scratch_dir(&self) -> io::Result<PathBuf> {
+        self.strategy.scratch_dir()
+    }
+
+    fn config_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (config_dir, source) = self.strategy.config_dir_with_source();
+        (self.config(config_dir), source)
+    }
+
+    fn data_dir_with_source(&self) -> (PathBuf, DirSource) {
+        let (data_dir, source) = self.strategy.data_dir_with_source();
+        (data_dir.join(&self.version), source)
+    }
+
+    fn cache_dir_with_source(&self) -> (PathBuf, DirSource) {
+        self.strategy.cache_dir_with_source()
+    }
+
+    fn state_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.strategy
+            .state_dir_with_source()
+            .map(|(state_dir, source)| (state_dir.join(&self.version), source))
+    }
+
+    fn runtime_dir_with_source(&self) -> Option<(PathBuf, DirSource)> {
+        self.strategy.runtime_dir_with_source()
+    }
+}